@@ -0,0 +1,52 @@
+use crate::notes::*;
+
+/// ABC token for `note` in `octave`, where octave 4 is written in capitals
+/// ("C"), octave 5 in lowercase ("c"), and further octaves add "'" or ",".
+pub fn note_to_abc(note: &Note, octave: i8) -> String {
+    let (accidental, white_note) = match note {
+        Note::WhiteNote(white_note) => ("", white_note),
+        Note::Sharp(white_note) => ("^", white_note),
+        Note::Flat(white_note) => ("_", white_note),
+        Note::DoubleSharp(white_note) => ("^^", white_note),
+        Note::DoubleFlat(white_note) => ("__", white_note),
+    };
+    let letter = Note::WhiteNote(white_note.clone()).to_string();
+    let (letter, markers) = if octave >= 5 {
+        (letter.to_lowercase(), "'".repeat((octave - 5) as usize))
+    }
+    else {
+        (letter, ",".repeat((4 - octave as i16) as usize))
+    };
+    format!("{}{}{}", accidental, letter, markers)
+}
+
+pub fn pitches_to_abc(pitches: &[(Note, i8)]) -> String {
+    pitches.iter().map(|(note, octave)| note_to_abc(note, *octave)).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scales::*;
+
+    #[test]
+    fn test_note_to_abc() {
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), 4), "C");
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), 5), "c");
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), 6), "c'");
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), 2), "C,,");
+        assert_eq!(note_to_abc(&Note::Sharp(WhiteNote::F), 4), "^F");
+        assert_eq!(note_to_abc(&Note::Flat(WhiteNote::B), 5), "_b");
+        assert_eq!(note_to_abc(&Note::DoubleSharp(WhiteNote::F), 4), "^^F");
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), -128), format!("C{}", ",".repeat(132)));
+        assert_eq!(note_to_abc(&Note::WhiteNote(WhiteNote::C), 127), format!("c{}", "'".repeat(122)));
+    }
+
+    #[test]
+    fn test_scale_to_abc() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(pitches_to_abc(&scale.pitches_over_octaves(1, 4).unwrap()), "C D E F G A B c");
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Major);
+        assert_eq!(pitches_to_abc(&scale.pitches_over_octaves(1, 4).unwrap()), "A B ^c d e ^f ^g a");
+    }
+}
@@ -4,88 +4,662 @@ use regex::Regex;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 use crate::notes::*;
+use crate::guitar::*;
+use crate::scales::*;
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChordQuality {
     Major,
     Minor,
+    Diminished,
     DominantSeventh,
     MajorSeventh,
     MinorSeventh,
+    HalfDiminished,
+    DiminishedSeventh,
+    Major6,
+    Minor6,
+    Add9,
+    SixNine,
+    Power,
+    #[strum(disabled)]
+    Custom(Vec<Interval>),
 }
 
+const QUALITY_PATTERN: &str = r"(?:(?i:maj7|add9|dim7|dim)|m7b5|M7|Δ7?|m7|m6|6/9|7|6|5|m)";
+const ALTERATION_PATTERN: &str = r"(?:[#b][59])";
+const OMISSION_PATTERN: &str = r"(?:\(no[35]\))";
+
+#[derive(Debug, PartialEq)]
+pub enum ChordParseError {
+    Empty,
+    InvalidRoot,
+    UnknownQuality,
+}
+
+impl fmt::Display for ChordParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChordParseError::Empty => write!(f, "empty chord"),
+            ChordParseError::InvalidRoot => write!(f, "invalid root note"),
+            ChordParseError::UnknownQuality => write!(f, "unknown chord quality"),
+        }
+    }
+}
+
+impl std::error::Error for ChordParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alteration {
+    FlatFive,
+    SharpFive,
+    FlatNine,
+    SharpNine,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Omission {
+    Third,
+    Fifth,
+}
+
+impl fmt::Display for Omission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Omission::Third => write!(f, "(no3)"),
+            Omission::Fifth => write!(f, "(no5)"),
+        }
+    }
+}
+
+/// Chords compare and hash through `Note`, so enharmonic roots such as C# and
+/// Db give equal chords when the quality and alterations match. A bass other
+/// than the root comes from an inversion or a slash chord.
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Chord {
     root: Note,
     quality: ChordQuality,
+    alterations: Vec<Alteration>,
+    omissions: Vec<Omission>,
+    bass: Option<Note>,
 }
 
-impl fmt::Display for Chord {
+impl fmt::Display for Alteration {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let quality = match self.quality {
+        match self {
+            Alteration::FlatFive => write!(f, "b5"),
+            Alteration::SharpFive => write!(f, "#5"),
+            Alteration::FlatNine => write!(f, "b9"),
+            Alteration::SharpNine => write!(f, "#9"),
+        }
+    }
+}
+
+impl ChordQuality {
+    pub fn symbol(&self) -> &'static str {
+        match self {
             ChordQuality::Major => "",
             ChordQuality::Minor => "m",
+            ChordQuality::Diminished => "dim",
             ChordQuality::DominantSeventh => "7",
             ChordQuality::MajorSeventh => "maj7",
             ChordQuality::MinorSeventh => "m7",
-        };
-        write!(f, "{}{}", self.root, quality)
+            ChordQuality::HalfDiminished => "m7b5",
+            ChordQuality::DiminishedSeventh => "dim7",
+            ChordQuality::Major6 => "6",
+            ChordQuality::Minor6 => "m6",
+            ChordQuality::Add9 => "add9",
+            ChordQuality::SixNine => "6/9",
+            ChordQuality::Power => "5",
+            ChordQuality::Custom(_) => "",
+        }
+    }
+
+    /// Named quality written as `symbol` by `ChordQuality::symbol`.
+    pub fn from_symbol(symbol: &str) -> Option<ChordQuality> {
+        ChordQuality::all_symbols().into_iter()
+            .find(|(_, other)| *other == symbol)
+            .map(|(quality, _)| quality)
+    }
+
+    /// Every named quality with its canonical symbol. Custom qualities have none.
+    pub fn all_symbols() -> Vec<(ChordQuality, &'static str)> {
+        ChordQuality::iter().map(|quality| {
+            let symbol = quality.symbol();
+            (quality, symbol)
+        }).collect()
+    }
+}
+
+impl fmt::Display for ChordQuality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            ChordQuality::Major => "Major",
+            ChordQuality::Minor => "Minor",
+            ChordQuality::Diminished => "Diminished",
+            ChordQuality::DominantSeventh => "Dominant Seventh",
+            ChordQuality::MajorSeventh => "Major Seventh",
+            ChordQuality::MinorSeventh => "Minor Seventh",
+            ChordQuality::HalfDiminished => "Half Diminished",
+            ChordQuality::DiminishedSeventh => "Diminished Seventh",
+            ChordQuality::Major6 => "Major Sixth",
+            ChordQuality::Minor6 => "Minor Sixth",
+            ChordQuality::Add9 => "Added Ninth",
+            ChordQuality::SixNine => "Six Nine",
+            ChordQuality::Power => "Power",
+            ChordQuality::Custom(_) => "Custom",
+        })
+    }
+}
+
+impl fmt::Display for Chord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.canonical_symbol())
     }
 }
 
 impl Chord {
     pub fn new(root: Note, quality: ChordQuality) -> Chord {
-        Chord { root, quality }
+        Chord { root, quality, alterations: Vec::new(), omissions: Vec::new(), bass: None }
     }
 
-    pub fn from_str(s: &str) -> Option<Chord> {
-        let re = Regex::new(r"([A-Ga-g][#b]?)((?:maj7|m7|7|m)?)").unwrap();
-        let caps = re.captures(s)?;
-        let root = Note::from_str(caps.get(1)?.as_str())?;
-        let quality = match caps.get(2)?.as_str() {
+    pub fn with_alterations(root: Note, quality: ChordQuality, alterations: Vec<Alteration>) -> Chord {
+        Chord { root, quality, alterations, omissions: Vec::new(), bass: None }
+    }
+
+    pub fn with_omissions(root: Note, quality: ChordQuality, alterations: Vec<Alteration>, omissions: Vec<Omission>) -> Chord {
+        Chord { root, quality, alterations, omissions, bass: None }
+    }
+
+    pub fn with_bass(&self, bass: Note) -> Chord {
+        let bass = if bass == self.root { None } else { Some(bass) };
+        Chord { bass, ..self.clone() }
+    }
+
+    /// Puts the `n`-th chord tone in the bass, 0 being root position, or
+    /// returns `None` if the chord has fewer tones.
+    pub fn inversion(&self, n: u8) -> Option<Chord> {
+        self.get_notes().get(n as usize).map(|note| self.with_bass(note.clone()))
+    }
+
+    /// Figured-bass numerals for the `inversion`-th inversion of a triad or
+    /// seventh chord, e.g. "6/4" for a second-inversion triad. Returns `None`
+    /// for other qualities, added ninths, or an inversion whose bass is omitted.
+    pub fn figured_bass(&self, inversion: u8) -> Option<String> {
+        let seventh = match self.quality {
+            ChordQuality::Major | ChordQuality::Minor | ChordQuality::Diminished => false,
+            ChordQuality::DominantSeventh | ChordQuality::MajorSeventh | ChordQuality::MinorSeventh
+                | ChordQuality::HalfDiminished | ChordQuality::DiminishedSeventh => true,
+            _ => return None,
+        };
+        if self.alterations.iter().any(|alteration| matches!(alteration, Alteration::FlatNine | Alteration::SharpNine)) {
+            return None;
+        }
+        let bass = match inversion {
+            1 => Some(Omission::Third),
+            2 => Some(Omission::Fifth),
+            _ => None,
+        };
+        if bass.is_some_and(|bass| self.omissions.contains(&bass)) {
+            return None;
+        }
+        let figures = match (seventh, inversion) {
+            (false, 0) => "",
+            (false, 1) => "6",
+            (false, 2) => "6/4",
+            (true, 0) => "7",
+            (true, 1) => "6/5",
+            (true, 2) => "4/3",
+            (true, 3) => "4/2",
+            _ => return None,
+        };
+        Some(figures.to_string())
+    }
+
+    /// Lowest note of the voicing: the slash bass or inverted tone if any, the root otherwise.
+    pub fn bass_note(&self) -> Note {
+        self.bass.clone().unwrap_or(self.root.clone())
+    }
+
+    pub fn root(&self) -> &Note {
+        &self.root
+    }
+
+    pub fn quality(&self) -> &ChordQuality {
+        &self.quality
+    }
+
+    /// Builds the chord made of `root` and the notes `intervals` above it, named
+    /// after a known quality when one has the same notes.
+    pub fn from_intervals(root: Note, intervals: &[Interval]) -> Chord {
+        let chord = Chord::new(root.clone(), ChordQuality::Custom(intervals.to_vec()));
+        let notes: HashSet<Note> = chord.get_notes().into_iter().collect();
+        ChordQuality::iter()
+            .map(|quality| Chord::new(root.clone(), quality))
+            .find(|known| {
+                let known_notes = known.get_notes();
+                known_notes.len() == notes.len() && known_notes.iter().all(|note| notes.contains(note))
+            })
+            .unwrap_or(chord)
+    }
+
+    /// The one symbol used for the chord whatever spelling it was parsed from,
+    /// e.g. "Cmaj7" for "CM7", "CΔ" and "Cmaj7". Custom chords list their notes.
+    pub fn canonical_symbol(&self) -> String {
+        if let ChordQuality::Custom(_) = self.quality {
+            return self.get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+        }
+        let alterations: String = self.alterations.iter().map(|alteration| alteration.to_string()).collect();
+        let omissions: String = self.omissions.iter().map(|omission| omission.to_string()).collect();
+        let bass = self.bass.as_ref().map(|bass| format!("/{}", bass)).unwrap_or_default();
+        format!("{}{}{}{}{}", self.root, self.quality.symbol(), alterations, omissions, bass)
+    }
+
+    fn parse_quality(s: &str) -> ChordQuality {
+        if matches!(s, "M7" | "Δ" | "Δ7") {
+            return ChordQuality::MajorSeventh;
+        }
+        match s.to_lowercase().as_str() {
             "maj7" => ChordQuality::MajorSeventh,
+            "m7b5" => ChordQuality::HalfDiminished,
             "m7" => ChordQuality::MinorSeventh,
+            "m6" => ChordQuality::Minor6,
+            "6/9" => ChordQuality::SixNine,
+            "add9" => ChordQuality::Add9,
             "7" => ChordQuality::DominantSeventh,
+            "6" => ChordQuality::Major6,
+            "5" => ChordQuality::Power,
+            "dim7" => ChordQuality::DiminishedSeventh,
+            "dim" => ChordQuality::Diminished,
             "m" => ChordQuality::Minor,
             _ => ChordQuality::Major,
-        };
-        Some(Chord::new(root, quality))
+        }
+    }
+
+    fn parse_alterations(s: &str) -> Vec<Alteration> {
+        s.as_bytes().chunks(2).filter_map(|alteration| match alteration {
+            b"b5" => Some(Alteration::FlatFive),
+            b"#5" => Some(Alteration::SharpFive),
+            b"b9" => Some(Alteration::FlatNine),
+            b"#9" => Some(Alteration::SharpNine),
+            _ => None,
+        }).collect()
+    }
+
+    fn parse_omissions(s: &str) -> Vec<Omission> {
+        s.split_terminator(')').filter_map(|omission| match omission {
+            "(no3" => Some(Omission::Third),
+            "(no5" => Some(Omission::Fifth),
+            _ => None,
+        }).collect()
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Chord> {
+        let re = Regex::new(&format!(r"^([A-Ga-g][#b]?)({}?)({}*)({}*)(?:/([A-Ga-g][#b]?))?$", QUALITY_PATTERN, ALTERATION_PATTERN, OMISSION_PATTERN)).unwrap();
+        let caps = re.captures(s.trim())?;
+        let root = Note::from_str(caps.get(1)?.as_str())?;
+        let quality = Chord::parse_quality(caps.get(2)?.as_str());
+        let alterations = Chord::parse_alterations(caps.get(3)?.as_str());
+        if !alterations.is_empty() && quality != ChordQuality::DominantSeventh {
+            return None;
+        }
+        let omissions = Chord::parse_omissions(caps.get(4)?.as_str());
+        let chord = Chord::with_omissions(root, quality, alterations, omissions);
+        match caps.get(5) {
+            Some(bass) => Some(chord.with_bass(Note::from_str(bass.as_str())?)),
+            None => Some(chord),
+        }
+    }
+
+    pub fn try_from_str(s: &str) -> Result<Chord, ChordParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ChordParseError::Empty);
+        }
+        let re = Regex::new(r"^([A-Ga-g][#b]?)(.*)$").unwrap();
+        let caps = re.captures(s).ok_or(ChordParseError::InvalidRoot)?;
+        let root = Note::from_str(&caps[1]).ok_or(ChordParseError::InvalidRoot)?;
+        let quality_re = Regex::new(&format!("^({}?)({}*)({}*)(?:/(.*))?$", QUALITY_PATTERN, ALTERATION_PATTERN, OMISSION_PATTERN)).unwrap();
+        let quality_caps = quality_re.captures(&caps[2]).ok_or(ChordParseError::UnknownQuality)?;
+        let quality = Chord::parse_quality(&quality_caps[1]);
+        let alterations = Chord::parse_alterations(&quality_caps[2]);
+        if !alterations.is_empty() && quality != ChordQuality::DominantSeventh {
+            return Err(ChordParseError::UnknownQuality);
+        }
+        let omissions = Chord::parse_omissions(&quality_caps[3]);
+        let chord = Chord::with_omissions(root, quality, alterations, omissions);
+        match quality_caps.get(4) {
+            Some(bass) => Ok(chord.with_bass(Note::from_str(bass.as_str()).ok_or(ChordParseError::InvalidRoot)?)),
+            None => Ok(chord),
+        }
     }
 
     pub fn get_notes(&self) -> Vec<Note> {
-        match self.quality {
+        let mut notes = match &self.quality {
             ChordQuality::Major => {
-                let major_third = self.root.up_interval(Interval::new(IntervalQuality::Major, 3));
-                let perfect_fifth = self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5));
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
                 vec![self.root.clone(), major_third, perfect_fifth]
             },
             ChordQuality::Minor => {
-                let minor_third = self.root.up_interval(Interval::new(IntervalQuality::Minor, 3));
-                let perfect_fifth = self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5));
+                let minor_third = self.root.up(Interval::minor_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
                 vec![self.root.clone(), minor_third, perfect_fifth]
             },
+            ChordQuality::Diminished => {
+                let minor_third = self.root.up(Interval::minor_third());
+                let diminished_fifth = self.root.up(Interval::diminished_fifth());
+                vec![self.root.clone(), minor_third, diminished_fifth]
+            },
             ChordQuality::DominantSeventh => {
-                let major_third = self.root.up_interval(Interval::new(IntervalQuality::Major, 3));
-                let perfect_fifth = self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5));
-                let minor_seventh = self.root.up_interval(Interval::new(IntervalQuality::Minor, 7));
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let minor_seventh = self.root.up(Interval::minor_seventh());
                 vec![self.root.clone(), major_third, perfect_fifth, minor_seventh]
             },
             ChordQuality::MajorSeventh => {
-                let major_third = self.root.up_interval(Interval::new(IntervalQuality::Major, 3));
-                let perfect_fifth = self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5));
-                let major_seventh = self.root.up_interval(Interval::new(IntervalQuality::Major, 7));
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let major_seventh = self.root.up(Interval::major_seventh());
                 vec![self.root.clone(), major_third, perfect_fifth, major_seventh]
             },
             ChordQuality::MinorSeventh => {
-                let minor_third = self.root.up_interval(Interval::new(IntervalQuality::Minor, 3));
-                let perfect_fifth = self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5));
-                let minor_seventh = self.root.up_interval(Interval::new(IntervalQuality::Minor, 7));
+                let minor_third = self.root.up(Interval::minor_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let minor_seventh = self.root.up(Interval::minor_seventh());
                 vec![self.root.clone(), minor_third, perfect_fifth, minor_seventh]
             },
+            ChordQuality::HalfDiminished => {
+                let minor_third = self.root.up(Interval::minor_third());
+                let diminished_fifth = self.root.up(Interval::diminished_fifth());
+                let minor_seventh = self.root.up(Interval::minor_seventh());
+                vec![self.root.clone(), minor_third, diminished_fifth, minor_seventh]
+            },
+            ChordQuality::DiminishedSeventh => {
+                let minor_third = self.root.up(Interval::minor_third());
+                let diminished_fifth = self.root.up(Interval::diminished_fifth());
+                let diminished_seventh = self.root.up(Interval::diminished_seventh());
+                vec![self.root.clone(), minor_third, diminished_fifth, diminished_seventh]
+            },
+            ChordQuality::Major6 => {
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let major_sixth = self.root.up(Interval::major_sixth());
+                vec![self.root.clone(), major_third, perfect_fifth, major_sixth]
+            },
+            ChordQuality::Minor6 => {
+                let minor_third = self.root.up(Interval::minor_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let major_sixth = self.root.up(Interval::major_sixth());
+                vec![self.root.clone(), minor_third, perfect_fifth, major_sixth]
+            },
+            ChordQuality::Add9 => {
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let major_ninth = self.root.up(Interval::major_second());
+                vec![self.root.clone(), major_third, perfect_fifth, major_ninth]
+            },
+            ChordQuality::SixNine => {
+                let major_third = self.root.up(Interval::major_third());
+                let perfect_fifth = self.root.up(Interval::perfect_fifth());
+                let major_sixth = self.root.up(Interval::major_sixth());
+                let major_ninth = self.root.up(Interval::major_second());
+                vec![self.root.clone(), major_third, perfect_fifth, major_sixth, major_ninth]
+            },
+            ChordQuality::Power => vec![self.root.clone(), self.root.up(Interval::perfect_fifth())],
+            ChordQuality::Custom(intervals) => {
+                let mut notes = vec![self.root.clone()];
+                notes.extend(intervals.iter().map(|interval| self.root.up(interval.clone())));
+                notes
+            },
+        };
+        for alteration in self.alterations.iter() {
+            match alteration {
+                Alteration::FlatFive | Alteration::SharpFive => {
+                    let fifth = if *alteration == Alteration::FlatFive {
+                        self.root.up(Interval::diminished_fifth())
+                    }
+                    else {
+                        self.root.up(Interval::augmented_fifth())
+                    };
+                    match notes.iter().position(|note| self.root.generic_interval(note) == 5) {
+                        Some(index) => notes[index] = fifth,
+                        None => notes.push(fifth),
+                    }
+                },
+                Alteration::FlatNine => notes.push(self.root.up(Interval::minor_second())),
+                Alteration::SharpNine => notes.push(self.root.up(Interval::augmented_second())),
+            }
+        }
+        for omission in self.omissions.iter() {
+            let number = match omission {
+                Omission::Third => 3,
+                Omission::Fifth => 5,
+            };
+            notes.retain(|note| self.root.generic_interval(note) != number);
+        }
+        notes
+    }
+
+    pub fn transpose(&self, interval: Interval) -> Chord {
+        let bass = self.bass.as_ref().map(|bass| bass.up(interval.clone()));
+        Chord { root: self.root.up(interval), bass, ..self.clone() }
+    }
+
+    pub fn transpose_semitones(&self, n: i8) -> Chord {
+        let bass = self.bass.as_ref().map(|bass| bass.transpose_semitones(n));
+        Chord { root: self.root.transpose_semitones(n), bass, ..self.clone() }
+    }
+
+    /// Spells the root and bass as they appear in `key`, or otherwise with the
+    /// key's accidentals. Keys without accidentals keep the original spelling.
+    pub fn respell_for_key(&self, key: &Scale) -> Chord {
+        let key_notes = key.get_notes();
+        let use_sharps = if key_notes.iter().any(|note| matches!(note, Note::Sharp(_) | Note::DoubleSharp(_))) {
+            Some(true)
+        }
+        else if key_notes.iter().any(|note| matches!(note, Note::Flat(_) | Note::DoubleFlat(_))) {
+            Some(false)
+        }
+        else {
+            None
+        };
+        let respell = |note: &Note| match (key_notes.iter().find(|key_note| *key_note == note), use_sharps) {
+            (Some(key_note), _) => key_note.clone(),
+            (None, Some(use_sharps)) => note.respell(use_sharps),
+            (None, None) => note.clone(),
+        };
+        Chord { root: respell(&self.root), bass: self.bass.as_ref().map(respell), ..self.clone() }
+    }
+
+    pub fn tritone_sub(&self) -> Option<Chord> {
+        match self.quality {
+            ChordQuality::DominantSeventh => Some(self.transpose(Interval::diminished_fifth())),
+            _ => None,
+        }
+    }
+
+    /// Reflects the chord around the axis between the tonic and the dominant of
+    /// `key`, naming the result after the reflected notes read downwards.
+    pub fn negative_harmony(&self, key: &Scale) -> Chord {
+        let tonic = key.root().get_index();
+        let mut reflected: Vec<Note> = self.get_notes().iter()
+            .map(|note| Note::from_index((2 * tonic + 7 + 12 - note.get_index()) % 12))
+            .collect();
+        reflected.reverse();
+        let reflected_set: HashSet<Note> = reflected.iter().cloned().collect();
+        for root in reflected.iter() {
+            for quality in ChordQuality::iter() {
+                let chord = Chord::new(root.clone(), quality);
+                let notes = chord.get_notes();
+                if notes.len() == reflected_set.len() && notes.iter().all(|note| reflected_set.contains(note)) {
+                    return chord;
+                }
+            }
+        }
+        let intervals: Vec<Interval> = reflected[1..].iter().filter_map(|note| Interval::between(&reflected[0], note)).collect();
+        Chord::from_intervals(reflected[0].clone(), &intervals)
+    }
+
+    pub fn close_voicing(&self, octave: i8) -> Vec<(Note, i8)> {
+        let mut notes = self.get_notes();
+        let bass = self.bass_note();
+        match notes.iter().position(|note| *note == bass) {
+            Some(index) => notes.rotate_left(index),
+            None => notes.insert(0, bass),
+        }
+        let mut voicing: Vec<(Note, i8)> = Vec::new();
+        for note in notes {
+            let note_octave = match voicing.last() {
+                Some((previous, previous_octave)) if note.to_midi(4) <= previous.to_midi(4) => previous_octave + 1,
+                Some((_, previous_octave)) => *previous_octave,
+                None => octave,
+            };
+            voicing.push((note, note_octave));
         }
+        voicing
     }
 
-    pub fn reverse_lookup(notes: &Vec<Note>) -> HashSet<Chord> {
+    /// Close voicing with the `which`-th voice from the top dropped an octave,
+    /// or `None` if the chord has no such voice.
+    pub fn drop_voicing(&self, which: u8, octave: i8) -> Option<Vec<(Note, i8)>> {
+        let mut voicing = self.close_voicing(octave);
+        if which == 0 || which as usize > voicing.len() {
+            return None;
+        }
+        let index = voicing.len() - which as usize;
+        voicing[index].1 = voicing[index].1.checked_sub(1)?;
+        voicing.sort_by_key(|(note, octave)| (*octave, note.to_midi(4)));
+        Some(voicing)
+    }
+
+    /// Playable shapes up to `max_fret`, from the low E string to the high one,
+    /// with the root in the bass and only chord tones ringing.
+    pub fn guitar_shapes(&self, max_fret: u8) -> Vec<Shape> {
+        let notes = self.get_notes();
+        let mut shapes: Vec<Shape> = vec![[None; 6]];
+        for string in 0..6 {
+            let frets: Vec<Option<u8>> = std::iter::once(None)
+                .chain((0..=max_fret).filter(|fret| notes.contains(&fret_note(string, *fret))).map(Some))
+                .collect();
+            shapes = shapes.iter()
+                .flat_map(|shape| frets.iter().map(move |fret| {
+                    let mut shape = *shape;
+                    shape[string] = *fret;
+                    shape
+                }))
+                .filter(|shape| is_playable_prefix(&shape[..=string]))
+                .collect();
+        }
+        shapes.retain(|shape| {
+            let sounding = sounding_notes(shape);
+            sounding.first() == Some(&self.root)
+                && notes.iter().all(|note| sounding.contains(note))
+                && is_playable(shape)
+        });
+        shapes.sort_by_key(|shape| shape.iter().flatten().max().copied());
+        shapes
+    }
+
+    pub fn contains(&self, note: &Note) -> bool {
+        self.get_notes().contains(note)
+    }
+
+    /// Whether the chord is made of exactly the pitch classes of `notes`, in any order.
+    pub fn has_notes(&self, notes: &[Note]) -> bool {
+        let chord_notes: HashSet<Note> = self.get_notes().into_iter().collect();
+        let notes: HashSet<Note> = notes.iter().cloned().collect();
+        chord_notes == notes
+    }
+
+    /// Five-limit just intonation ratio of each chord tone to the root.
+    pub fn just_intonation_ratios(&self) -> Vec<(u32, u32)> {
+        self.get_notes().iter().map(|note| match self.root.semitone_distance(note) {
+            0 => (1, 1),
+            1 => (16, 15),
+            2 => (9, 8),
+            3 => (6, 5),
+            4 => (5, 4),
+            5 => (4, 3),
+            6 => (45, 32),
+            7 => (3, 2),
+            8 => (8, 5),
+            9 => (5, 3),
+            10 => (9, 5),
+            _ => (15, 8),
+        }).collect()
+    }
+
+    pub fn chord_tones(&self) -> Vec<Note> {
+        self.get_notes()
+    }
+
+    /// Notes of `key` outside the chord lying a 9th, 11th or 13th above the
+    /// root, paired with that number.
+    pub fn available_tensions(&self, key: &Scale) -> Vec<(u8, Note)> {
+        let chord_tones = self.chord_tones();
+        let mut tensions: Vec<(u8, Note)> = key.get_notes().into_iter()
+            .filter(|note| !chord_tones.contains(note))
+            .map(|note| (self.root.generic_interval(&note) + 7, note))
+            .filter(|(number, _)| matches!(number, 9 | 11 | 13))
+            .collect();
+        tensions.sort_by_key(|(number, _)| *number);
+        tensions
+    }
+
+    /// First pair of chord tones a tritone apart, e.g. the 3rd and 7th of a dominant seventh.
+    pub fn tritone(&self) -> Option<(Note, Note)> {
+        let notes = self.get_notes();
+        notes.iter().enumerate().find_map(|(i, lower)| {
+            notes[i + 1..].iter()
+                .find(|upper| lower.semitone_distance(upper) == 6)
+                .map(|upper| (lower.clone(), upper.clone()))
+        })
+    }
+
+    pub fn common_tones(&self, other: &Chord) -> Vec<Note> {
+        let other_notes = other.get_notes();
+        self.get_notes().into_iter().filter(|note| other_notes.contains(note)).collect()
+    }
+
+    /// Roman numeral of the chord on `degree` of a key, or `None` outside 1 to 7.
+    pub fn roman_numeral(&self, degree: u8) -> Option<String> {
+        let numeral = match degree {
+            1 => "I",
+            2 => "II",
+            3 => "III",
+            4 => "IV",
+            5 => "V",
+            6 => "VI",
+            7 => "VII",
+            _ => return None,
+        };
+        let (minor, suffix) = match &self.quality {
+            ChordQuality::Major => (false, ""),
+            ChordQuality::Minor => (true, ""),
+            ChordQuality::Diminished => (true, "°"),
+            ChordQuality::DominantSeventh => (false, "7"),
+            ChordQuality::MajorSeventh => (false, "maj7"),
+            ChordQuality::MinorSeventh => (true, "7"),
+            ChordQuality::HalfDiminished => (true, "ø7"),
+            ChordQuality::DiminishedSeventh => (true, "°7"),
+            ChordQuality::Major6 => (false, "6"),
+            ChordQuality::Minor6 => (true, "6"),
+            ChordQuality::Add9 => (false, "add9"),
+            ChordQuality::SixNine => (false, "6/9"),
+            ChordQuality::Power => (false, "5"),
+            ChordQuality::Custom(intervals) => (intervals.contains(&Interval::minor_third()), ""),
+        };
+        let numeral = if minor { numeral.to_lowercase() } else { numeral.to_string() };
+        Some(format!("{}{}", numeral, suffix))
+    }
+
+    pub fn reverse_lookup(notes: &[Note]) -> HashSet<Chord> {
         let mut possible_chords = HashSet::new();
         for white_note in WhiteNote::iter() {
             for root in [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::Flat(white_note)].iter() {
@@ -100,6 +674,52 @@ impl Chord {
         }
         possible_chords
     }
+
+    /// Chords containing `notes`, best fit first. The score is the share of the
+    /// chord's pitch classes found in `notes`, so extra chord tones lower it and
+    /// an exact match scores 1. Ties go to chords rooted on the first note.
+    pub fn reverse_lookup_ranked(notes: &[Note]) -> Vec<(Chord, f64)> {
+        let input: HashSet<Note> = notes.iter().cloned().collect();
+        let mut ranked: Vec<(Chord, f64)> = Chord::reverse_lookup(notes).into_iter().map(|chord| {
+            let chord_notes: HashSet<Note> = chord.get_notes().into_iter().collect();
+            let score = chord_notes.intersection(&input).count() as f64 / chord_notes.len() as f64;
+            (chord, score)
+        }).collect();
+        ranked.sort_by(|(a, a_score), (b, b_score)| {
+            b_score.total_cmp(a_score)
+                .then_with(|| (Some(&a.root) != notes.first()).cmp(&(Some(&b.root) != notes.first())))
+                .then_with(|| a.to_string().cmp(&b.to_string()))
+        });
+        ranked
+    }
+}
+
+fn pitch_class_distance(a: &Note, b: &Note) -> u8 {
+    let distance = a.semitone_distance(b);
+    distance.min(12 - distance)
+}
+
+fn minimal_matching(from: &[Note], to: &[Note], reached: &mut Vec<bool>) -> Option<u8> {
+    match from.split_first() {
+        Some((note, rest)) => (0..to.len()).filter_map(|i| {
+            let already_reached = reached[i];
+            reached[i] = true;
+            let distance = minimal_matching(rest, to, reached).map(|distance| distance + pitch_class_distance(note, &to[i]));
+            reached[i] = already_reached;
+            distance
+        }).min(),
+        None => reached.iter().all(|r| *r).then_some(0),
+    }
+}
+
+/// Minimal total semitone motion between the tones of two chords. When the
+/// chords differ in size, several voices may move to the same tone.
+pub fn voice_leading_distance(a: &Chord, b: &Chord) -> u8 {
+    let (mut larger, mut smaller) = (a.get_notes(), b.get_notes());
+    if larger.len() < smaller.len() {
+        std::mem::swap(&mut larger, &mut smaller);
+    }
+    minimal_matching(&larger, &smaller, &mut vec![false; smaller.len()]).unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -116,6 +736,10 @@ mod tests {
         assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
         assert_eq!(chord.quality, ChordQuality::Minor);
 
+        let chord = Chord::from_str("Cdim").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::Diminished);
+
         let chord = Chord::from_str("C7").unwrap();
         assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
         assert_eq!(chord.quality, ChordQuality::DominantSeventh);
@@ -127,6 +751,42 @@ mod tests {
         let chord = Chord::from_str("Cm7").unwrap();
         assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
         assert_eq!(chord.quality, ChordQuality::MinorSeventh);
+
+        let chord = Chord::from_str("Bm7b5").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::B));
+        assert_eq!(chord.quality, ChordQuality::HalfDiminished);
+
+        let chord = Chord::from_str("Bdim7").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::B));
+        assert_eq!(chord.quality, ChordQuality::DiminishedSeventh);
+
+        let chord = Chord::from_str("CMaj7").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::MajorSeventh);
+
+        let chord = Chord::from_str("cDIM").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::Diminished);
+
+        let chord = Chord::from_str("ebm7").unwrap();
+        assert_eq!(chord.root, Note::Flat(WhiteNote::E));
+        assert_eq!(chord.quality, ChordQuality::MinorSeventh);
+
+        let chord = Chord::from_str("C6").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::Major6);
+
+        let chord = Chord::from_str("Cm6").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::Minor6);
+
+        let chord = Chord::from_str("Cadd9").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::Add9);
+
+        let chord = Chord::from_str("C6/9").unwrap();
+        assert_eq!(chord.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.quality, ChordQuality::SixNine);
     }
 
     #[test]
@@ -159,6 +819,13 @@ mod tests {
         assert_eq!(notes[1], Note::WhiteNote(WhiteNote::F));
         assert_eq!(notes[2], Note::WhiteNote(WhiteNote::A));
 
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 3);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::B));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::D));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::F));
+
         let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::DominantSeventh);
         let notes = chord.get_notes();
         assert_eq!(notes.len(), 4);
@@ -182,6 +849,59 @@ mod tests {
         assert_eq!(notes[1], Note::Flat(WhiteNote::E));
         assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
         assert_eq!(notes[3], Note::Flat(WhiteNote::B));
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::HalfDiminished);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::B));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::D));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::F));
+        assert_eq!(notes[3], Note::WhiteNote(WhiteNote::A));
+        assert_eq!(chord.to_string(), "Bm7b5");
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::DiminishedSeventh);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::B));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::D));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::F));
+        assert_eq!(notes[3], Note::Flat(WhiteNote::A));
+        assert_eq!(notes[3].to_string(), "Ab");
+        assert_eq!(chord.to_string(), "Bdim7");
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major6);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::E));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
+        assert_eq!(notes[3], Note::WhiteNote(WhiteNote::A));
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Minor6);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::Flat(WhiteNote::E));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
+        assert_eq!(notes[3], Note::WhiteNote(WhiteNote::A));
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Add9);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::E));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
+        assert_eq!(notes[3], Note::WhiteNote(WhiteNote::D));
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::SixNine);
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 5);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::E));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
+        assert_eq!(notes[3], Note::WhiteNote(WhiteNote::A));
+        assert_eq!(notes[4], Note::WhiteNote(WhiteNote::D));
+        assert_eq!(chord.to_string(), "C6/9");
     }
 
     #[test]
@@ -202,4 +922,512 @@ mod tests {
         let chords = Chord::reverse_lookup(&notes);
         assert!(chords.contains(&Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor)));
     }
+
+    #[test]
+    fn test_chord_reverse_lookup_ranked() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let notes = vec![c.clone(), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G)];
+        let ranked = Chord::reverse_lookup_ranked(&notes);
+        assert_eq!(ranked[0], (Chord::new(c.clone(), ChordQuality::Major), 1.0));
+        let position = |chord: &Chord| ranked.iter().position(|(ranked_chord, _)| ranked_chord == chord).unwrap();
+        assert!(position(&Chord::new(c.clone(), ChordQuality::Major6)) > 0);
+        assert!(position(&Chord::new(c.clone(), ChordQuality::MajorSeventh)) > 0);
+        assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+        let notes = vec![c.clone(), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G), Note::WhiteNote(WhiteNote::A)];
+        let ranked = Chord::reverse_lookup_ranked(&notes);
+        assert_eq!(ranked[0].0, Chord::new(c, ChordQuality::Major6));
+        assert_eq!(ranked[1].0, Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::MinorSeventh));
+    }
+
+    #[test]
+    fn test_chord_roman_numeral() {
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major).roman_numeral(1), Some("I".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor).roman_numeral(2), Some("ii".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished).roman_numeral(7), Some("vii°".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh).roman_numeral(5), Some("V7".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MajorSeventh).roman_numeral(1), Some("Imaj7".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::MinorSeventh).roman_numeral(6), Some("vi7".to_string()));
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Diminished).roman_numeral(8), None);
+        assert_eq!(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major).roman_numeral(0), None);
+    }
+
+    #[test]
+    fn test_chord_transpose() {
+        let chord = Chord::from_str("Cmaj7").unwrap().transpose(Interval::new(IntervalQuality::Perfect, 5));
+        assert_eq!(chord.to_string(), "Gmaj7");
+        let chord = Chord::from_str("Am").unwrap().transpose(Interval::new(IntervalQuality::Major, 2));
+        assert_eq!(chord.to_string(), "Bm");
+        let chord = Chord::from_str("D7").unwrap().transpose(Interval::new(IntervalQuality::Minor, 3));
+        assert_eq!(chord.to_string(), "F7");
+        let chord = Chord::from_str("Eb").unwrap().transpose(Interval::new(IntervalQuality::Perfect, 4));
+        assert_eq!(chord.to_string(), "Ab");
+    }
+
+    #[test]
+    fn test_chord_transpose_semitones() {
+        assert_eq!(Chord::from_str("C").unwrap().transpose_semitones(2).to_string(), "D");
+        assert_eq!(Chord::from_str("Am").unwrap().transpose_semitones(3).to_string(), "Cm");
+        assert_eq!(Chord::from_str("G7").unwrap().transpose_semitones(-1).to_string(), "F#7");
+        assert_eq!(Chord::from_str("C").unwrap().transpose_semitones(-2).to_string(), "Bb");
+        assert_eq!(Chord::from_str("F").unwrap().transpose_semitones(-12).to_string(), "F");
+        assert_eq!(Chord::from_str("B").unwrap().transpose_semitones(120).to_string(), "B");
+    }
+
+    #[test]
+    fn test_chord_tritone_sub() {
+        assert_eq!(Chord::from_str("G7").unwrap().tritone_sub(), Chord::from_str("Db7"));
+        assert_eq!(Chord::from_str("G7").unwrap().tritone_sub().unwrap().to_string(), "Db7");
+        assert_eq!(Chord::from_str("C7").unwrap().tritone_sub(), Chord::from_str("Gb7"));
+        assert_eq!(Chord::from_str("D7").unwrap().tritone_sub(), Chord::from_str("Ab7"));
+        assert_eq!(Chord::from_str("B7").unwrap().tritone_sub(), Chord::from_str("F7"));
+        assert_eq!(Chord::from_str("C").unwrap().tritone_sub(), None);
+        assert_eq!(Chord::from_str("Cmaj7").unwrap().tritone_sub(), None);
+        assert_eq!(Chord::from_str("Dm7").unwrap().tritone_sub(), None);
+    }
+
+    #[test]
+    fn test_chord_close_voicing() {
+        let voicing = Chord::from_str("Cmaj7").unwrap().close_voicing(4);
+        assert_eq!(voicing, vec![
+            (Note::WhiteNote(WhiteNote::C), 4),
+            (Note::WhiteNote(WhiteNote::E), 4),
+            (Note::WhiteNote(WhiteNote::G), 4),
+            (Note::WhiteNote(WhiteNote::B), 4),
+        ]);
+
+        let voicing = Chord::from_str("A7").unwrap().close_voicing(3);
+        assert_eq!(voicing, vec![
+            (Note::WhiteNote(WhiteNote::A), 3),
+            (Note::Sharp(WhiteNote::C), 4),
+            (Note::WhiteNote(WhiteNote::E), 4),
+            (Note::WhiteNote(WhiteNote::G), 4),
+        ]);
+    }
+
+    #[test]
+    fn test_chord_drop_voicing() {
+        let voicing = Chord::from_str("Cmaj7").unwrap().drop_voicing(2, 4);
+        assert_eq!(voicing, Some(vec![
+            (Note::WhiteNote(WhiteNote::G), 3),
+            (Note::WhiteNote(WhiteNote::C), 4),
+            (Note::WhiteNote(WhiteNote::E), 4),
+            (Note::WhiteNote(WhiteNote::B), 4),
+        ]));
+
+        let voicing = Chord::from_str("Cmaj7").unwrap().drop_voicing(3, 4);
+        assert_eq!(voicing, Some(vec![
+            (Note::WhiteNote(WhiteNote::E), 3),
+            (Note::WhiteNote(WhiteNote::C), 4),
+            (Note::WhiteNote(WhiteNote::G), 4),
+            (Note::WhiteNote(WhiteNote::B), 4),
+        ]));
+
+        assert_eq!(Chord::from_str("C").unwrap().drop_voicing(0, 4), None);
+        assert_eq!(Chord::from_str("C").unwrap().drop_voicing(4, 4), None);
+    }
+
+    #[test]
+    fn test_chord_common_tones() {
+        let c = Chord::from_str("C").unwrap();
+        assert_eq!(c.common_tones(&Chord::from_str("Am").unwrap()), vec![Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E)]);
+        assert_eq!(c.common_tones(&Chord::from_str("Em").unwrap()), vec![Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G)]);
+        assert_eq!(c.common_tones(&Chord::from_str("G").unwrap()), vec![Note::WhiteNote(WhiteNote::G)]);
+        assert_eq!(c.common_tones(&Chord::from_str("F#m").unwrap()), vec![]);
+        assert_eq!(Chord::from_str("Ab").unwrap().common_tones(&Chord::from_str("E").unwrap()), vec![Note::Flat(WhiteNote::A)]);
+    }
+
+    #[test]
+    fn test_chord_try_from_str() {
+        assert_eq!(Chord::try_from_str("Cmaj7"), Ok(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MajorSeventh)));
+        assert_eq!(Chord::try_from_str(" F#m "), Ok(Chord::new(Note::Sharp(WhiteNote::F), ChordQuality::Minor)));
+        assert_eq!(Chord::try_from_str("Bb"), Ok(Chord::new(Note::Flat(WhiteNote::B), ChordQuality::Major)));
+        assert_eq!(Chord::try_from_str(""), Err(ChordParseError::Empty));
+        assert_eq!(Chord::try_from_str("   "), Err(ChordParseError::Empty));
+        assert_eq!(Chord::try_from_str("H"), Err(ChordParseError::InvalidRoot));
+        assert_eq!(Chord::try_from_str("#C"), Err(ChordParseError::InvalidRoot));
+        assert_eq!(Chord::try_from_str("Cxyz"), Err(ChordParseError::UnknownQuality));
+        assert_eq!(Chord::try_from_str("Cm7x"), Err(ChordParseError::UnknownQuality));
+    }
+
+    #[test]
+    fn test_chord_alterations() {
+        let chord = Chord::from_str("C7#9").unwrap();
+        assert_eq!(chord, Chord::with_alterations(Note::WhiteNote(WhiteNote::C), ChordQuality::DominantSeventh, vec![Alteration::SharpNine]));
+        let notes = chord.get_notes();
+        assert_eq!(notes.len(), 5);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::E));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::G));
+        assert_eq!(notes[3], Note::Flat(WhiteNote::B));
+        assert_eq!(notes[4].to_string(), "D#");
+        assert_eq!(chord.to_string(), "C7#9");
+
+        let notes = Chord::from_str("C7b9").unwrap().get_notes();
+        assert_eq!(notes[4].to_string(), "Db");
+
+        let notes = Chord::from_str("C7#5").unwrap().get_notes();
+        assert_eq!(notes.len(), 4);
+        assert_eq!(notes[2].to_string(), "G#");
+
+        let chord = Chord::with_alterations(Note::WhiteNote(WhiteNote::C), ChordQuality::Power, vec![Alteration::FlatFive]);
+        let notes: Vec<String> = chord.get_notes().iter().map(|note| note.to_string()).collect();
+        assert_eq!(notes, vec!["C", "Gb"]);
+
+        let chord = Chord::with_alterations(Note::WhiteNote(WhiteNote::C), ChordQuality::Custom(vec![Interval::major_third()]), vec![Alteration::SharpFive]);
+        let notes: Vec<String> = chord.get_notes().iter().map(|note| note.to_string()).collect();
+        assert_eq!(notes, vec!["C", "E", "G#"]);
+
+        let chord = Chord::from_str("G7b5b9").unwrap();
+        assert_eq!(chord.to_string(), "G7b5b9");
+        let notes = chord.get_notes();
+        assert_eq!(notes[2].to_string(), "Db");
+        assert_eq!(notes[4].to_string(), "Ab");
+
+        assert_eq!(Chord::from_str("Cm7b5").unwrap().quality, ChordQuality::HalfDiminished);
+        assert_eq!(Chord::from_str("CM7b5"), None);
+        assert_eq!(Chord::from_str("CM7B5"), None);
+        assert_eq!(Chord::from_str("Cmaj7#9"), None);
+        assert_eq!(Chord::try_from_str("C7#9").unwrap().to_string(), "C7#9");
+        assert_eq!(Chord::try_from_str("Cm#9"), Err(ChordParseError::UnknownQuality));
+        assert_eq!(Chord::from_str("C7#9").unwrap().transpose_semitones(2).to_string(), "D7#9");
+    }
+
+    #[test]
+    fn test_chord_enharmonic_equality() {
+        let c_sharp = Chord::new(Note::Sharp(WhiteNote::C), ChordQuality::Major);
+        let d_flat = Chord::new(Note::Flat(WhiteNote::D), ChordQuality::Major);
+        assert_eq!(c_sharp, d_flat);
+        assert_ne!(c_sharp, Chord::new(Note::Flat(WhiteNote::D), ChordQuality::Minor));
+
+        let chords: HashSet<Chord> = [c_sharp, d_flat].into_iter().collect();
+        assert_eq!(chords.len(), 1);
+    }
+
+    #[test]
+    fn test_quality_display() {
+        let names: Vec<String> = ChordQuality::iter().map(|quality| quality.to_string()).collect();
+        assert_eq!(names, vec![
+            "Major", "Minor", "Diminished", "Dominant Seventh", "Major Seventh", "Minor Seventh",
+            "Half Diminished", "Diminished Seventh", "Major Sixth", "Minor Sixth", "Added Ninth", "Six Nine", "Power",
+        ]);
+        let symbols: Vec<&str> = ChordQuality::iter().map(|quality| quality.symbol()).collect();
+        assert_eq!(symbols, vec!["", "m", "dim", "7", "maj7", "m7", "m7b5", "dim7", "6", "m6", "add9", "6/9", "5"]);
+    }
+
+    #[test]
+    fn test_guitar_shapes() {
+        let shapes = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major).guitar_shapes(5);
+        assert!(shapes.contains(&[None, Some(3), Some(2), Some(0), Some(1), Some(0)]));
+        assert!(shapes.iter().all(|shape| sounding_notes(shape).iter().all(|note| {
+            [Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G)].contains(note)
+        })));
+
+        let shapes = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major).guitar_shapes(5);
+        assert!(shapes.contains(&[Some(3), Some(2), Some(0), Some(0), Some(0), Some(3)]));
+
+        let shapes = Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major).guitar_shapes(5);
+        assert!(shapes.contains(&[Some(1), Some(3), Some(3), Some(2), Some(1), Some(1)]));
+
+        let shapes = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major).guitar_shapes(60);
+        assert!(shapes.contains(&[Some(8), Some(10), Some(10), Some(9), Some(8), Some(8)]));
+        assert!(shapes.contains(&[Some(56), Some(58), Some(58), Some(57), Some(56), Some(56)]));
+        assert!(shapes.iter().all(is_playable));
+    }
+
+    #[test]
+    fn test_voice_leading_distance() {
+        let c = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        let g = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major);
+        let f_sharp = Chord::new(Note::Sharp(WhiteNote::F), ChordQuality::Major);
+        let g7 = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh);
+        assert_eq!(voice_leading_distance(&c, &c), 0);
+        assert_eq!(voice_leading_distance(&c, &g), 3);
+        assert_eq!(voice_leading_distance(&c, &f_sharp), 6);
+        assert!(voice_leading_distance(&c, &g) < voice_leading_distance(&c, &f_sharp));
+        assert_eq!(voice_leading_distance(&g7, &c), voice_leading_distance(&c, &g7));
+        assert_eq!(voice_leading_distance(&g7, &c), 4);
+    }
+
+    #[test]
+    fn test_from_intervals() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let major_third = Interval::new(IntervalQuality::Major, 3);
+        let perfect_fifth = Interval::new(IntervalQuality::Perfect, 5);
+        let chord = Chord::from_intervals(c.clone(), &[major_third.clone(), perfect_fifth.clone()]);
+        assert_eq!(chord, Chord::new(c.clone(), ChordQuality::Major));
+        assert_eq!(chord.to_string(), "C");
+
+        let chord = Chord::from_intervals(c.clone(), &[perfect_fifth.clone(), major_third.clone(), Interval::new(IntervalQuality::Minor, 7)]);
+        assert_eq!(chord.to_string(), "C7");
+
+        let augmented_fourth = Interval::new(IntervalQuality::Augmented, 4);
+        let chord = Chord::from_intervals(c.clone(), &[major_third.clone(), augmented_fourth.clone()]);
+        assert_eq!(chord, Chord::new(c.clone(), ChordQuality::Custom(vec![major_third, augmented_fourth])));
+        assert_eq!(chord.get_notes(), vec![c, Note::WhiteNote(WhiteNote::E), Note::Sharp(WhiteNote::F)]);
+        assert_eq!(chord.to_string(), "C E F#");
+        assert_eq!(chord.roman_numeral(1), Some("I".to_string()));
+    }
+
+    #[test]
+    fn test_negative_harmony() {
+        let c_major = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        let g7 = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh);
+        assert_eq!(g7.negative_harmony(&c_major), Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::HalfDiminished));
+        let c = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        assert_eq!(c.negative_harmony(&c_major), Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Minor));
+        let f = Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major);
+        assert_eq!(f.negative_harmony(&c_major), Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Minor));
+        let g_major = Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Major);
+        let d7 = Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::DominantSeventh);
+        assert_eq!(d7.negative_harmony(&g_major), Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::HalfDiminished));
+    }
+
+    #[test]
+    fn test_major_seventh_symbols() {
+        let major_seventh = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MajorSeventh);
+        assert_eq!(Chord::from_str("CM7"), Some(major_seventh.clone()));
+        assert_eq!(Chord::from_str("CΔ"), Some(major_seventh.clone()));
+        assert_eq!(Chord::from_str("CΔ7"), Some(major_seventh.clone()));
+        assert_eq!(Chord::try_from_str("CM7"), Ok(major_seventh.clone()));
+        assert_eq!(Chord::try_from_str("CΔ"), Ok(major_seventh.clone()));
+        assert_eq!(Chord::from_str("Cmaj7"), Some(major_seventh));
+        assert_eq!(Chord::from_str("Cm7"), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MinorSeventh)));
+        assert_eq!(Chord::try_from_str("Cm7"), Ok(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MinorSeventh)));
+    }
+
+    #[test]
+    fn test_available_tensions() {
+        let c_major = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::MinorSeventh);
+        assert_eq!(chord.chord_tones(), chord.get_notes());
+        assert_eq!(chord.available_tensions(&c_major), vec![
+            (9, Note::WhiteNote(WhiteNote::E)),
+            (11, Note::WhiteNote(WhiteNote::G)),
+            (13, Note::WhiteNote(WhiteNote::B)),
+        ]);
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh);
+        assert_eq!(chord.available_tensions(&c_major), vec![
+            (9, Note::WhiteNote(WhiteNote::A)),
+            (11, Note::WhiteNote(WhiteNote::C)),
+            (13, Note::WhiteNote(WhiteNote::E)),
+        ]);
+
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major6);
+        assert_eq!(chord.available_tensions(&c_major), vec![
+            (9, Note::WhiteNote(WhiteNote::D)),
+            (11, Note::WhiteNote(WhiteNote::F)),
+        ]);
+    }
+
+    #[test]
+    fn test_has_notes() {
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        assert!(chord.has_notes(&[Note::WhiteNote(WhiteNote::G), Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E)]));
+        assert!(!chord.has_notes(&[Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E)]));
+        assert!(!chord.has_notes(&[Note::WhiteNote(WhiteNote::C), Note::Flat(WhiteNote::E), Note::WhiteNote(WhiteNote::G)]));
+
+        let chord = Chord::new(Note::Flat(WhiteNote::B), ChordQuality::DominantSeventh);
+        assert!(chord.has_notes(&[
+            Note::Flat(WhiteNote::A),
+            Note::WhiteNote(WhiteNote::F),
+            Note::WhiteNote(WhiteNote::D),
+            Note::Sharp(WhiteNote::A),
+        ]));
+        assert!(!chord.has_notes(&[Note::Flat(WhiteNote::B), Note::WhiteNote(WhiteNote::D), Note::WhiteNote(WhiteNote::F)]));
+    }
+
+    #[test]
+    fn test_just_intonation_ratios() {
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        assert_eq!(chord.just_intonation_ratios(), vec![(1, 1), (5, 4), (3, 2)]);
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor);
+        assert_eq!(chord.just_intonation_ratios(), vec![(1, 1), (6, 5), (3, 2)]);
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::MajorSeventh);
+        assert_eq!(chord.just_intonation_ratios(), vec![(1, 1), (5, 4), (3, 2), (15, 8)]);
+    }
+
+    #[test]
+    fn test_canonical_symbol() {
+        for symbol in ["Cmaj7", "CMAJ7", "CM7", "CΔ", "CΔ7"] {
+            assert_eq!(Chord::from_str(symbol).unwrap().canonical_symbol(), "Cmaj7");
+        }
+        assert_eq!(Chord::from_str("F#DIM").unwrap().canonical_symbol(), "F#dim");
+        assert_eq!(Chord::from_str("G7b9").unwrap().canonical_symbol(), "G7b9");
+        assert_eq!(Chord::from_str("Dm7").unwrap().canonical_symbol(), Chord::from_str("Dm7").unwrap().to_string());
+    }
+
+    #[test]
+    fn test_contains() {
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        assert!(chord.contains(&Note::WhiteNote(WhiteNote::E)));
+        assert!(chord.contains(&Note::Flat(WhiteNote::F)));
+        assert!(chord.contains(&Note::Sharp(WhiteNote::B)));
+        assert!(chord.contains(&Note::DoubleFlat(WhiteNote::A)));
+        assert!(!chord.contains(&Note::Flat(WhiteNote::E)));
+        assert!(!chord.contains(&Note::WhiteNote(WhiteNote::F)));
+    }
+
+    #[test]
+    fn test_chord_accessors() {
+        let chord = Chord::new(Note::Flat(WhiteNote::E), ChordQuality::MinorSeventh);
+        assert_eq!(chord.root(), &Note::Flat(WhiteNote::E));
+        assert_eq!(chord.quality(), &ChordQuality::MinorSeventh);
+    }
+
+    #[test]
+    fn test_power_chord() {
+        let chord = Chord::from_str("C5").unwrap();
+        assert_eq!(chord, Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Power));
+        assert_eq!(chord.get_notes(), vec![Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::G)]);
+        assert_eq!(chord.to_string(), "C5");
+        assert_eq!(Chord::try_from_str("F#5").unwrap().get_notes(), vec![Note::Sharp(WhiteNote::F), Note::Sharp(WhiteNote::C)]);
+        assert_eq!(Chord::try_from_str("Bb5").unwrap().to_string(), "Bb5");
+        assert_eq!(Chord::from_str("C7b5"), Some(Chord::with_alterations(Note::WhiteNote(WhiteNote::C), ChordQuality::DominantSeventh, vec![Alteration::FlatFive])));
+        assert_eq!(Chord::from_str("Cm7b5"), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::HalfDiminished)));
+    }
+
+    #[test]
+    fn test_omissions() {
+        let chord = Chord::from_str("Cmaj7(no5)").unwrap();
+        assert_eq!(chord, Chord::with_omissions(Note::WhiteNote(WhiteNote::C), ChordQuality::MajorSeventh, vec![], vec![Omission::Fifth]));
+        assert_eq!(chord.get_notes(), vec![Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::B)]);
+        assert_eq!(chord.to_string(), "Cmaj7(no5)");
+
+        let chord = Chord::try_from_str("C(no3)").unwrap();
+        assert_eq!(chord.get_notes(), vec![Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::G)]);
+        assert_eq!(chord.transpose_semitones(2).to_string(), "D(no3)");
+
+        let chord = Chord::try_from_str("G7b5(no3)").unwrap();
+        assert_eq!(chord.get_notes(), vec![Note::WhiteNote(WhiteNote::G), Note::Flat(WhiteNote::D), Note::WhiteNote(WhiteNote::F)]);
+        assert_eq!(Chord::try_from_str("C(no4)"), Err(ChordParseError::UnknownQuality));
+    }
+
+    #[test]
+    fn test_tritone() {
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh);
+        assert_eq!(chord.tritone(), Some((Note::WhiteNote(WhiteNote::B), Note::WhiteNote(WhiteNote::F))));
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished);
+        assert_eq!(chord.tritone(), Some((Note::WhiteNote(WhiteNote::B), Note::WhiteNote(WhiteNote::F))));
+        let chord = Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major);
+        assert_eq!(chord.tritone(), None);
+    }
+
+    #[test]
+    fn test_from_str_full_match() {
+        assert_eq!(Chord::from_str("Cxyz"), None);
+        assert_eq!(Chord::from_str("Cmaj7x"), None);
+        assert_eq!(Chord::from_str(" C "), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major)));
+        assert_eq!(Chord::from_str(" C ").ok_or(ChordParseError::Empty), Chord::try_from_str(" C "));
+        assert_eq!(Chord::from_str("C x"), None);
+        assert_eq!(Chord::from_str("Cmaj7"), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::MajorSeventh)));
+    }
+
+    #[test]
+    fn test_bass_note() {
+        let chord = Chord::from_str("C").unwrap();
+        assert_eq!(chord.bass_note(), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.inversion(0).unwrap().bass_note(), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(chord.inversion(1).unwrap().bass_note(), Note::WhiteNote(WhiteNote::E));
+        assert_eq!(chord.inversion(2).unwrap().bass_note(), Note::WhiteNote(WhiteNote::G));
+        assert_eq!(Chord::from_str("G7").unwrap().inversion(3).unwrap().bass_note(), Note::WhiteNote(WhiteNote::F));
+
+        let chord = Chord::from_str("C/E").unwrap();
+        assert_eq!(Some(chord.clone()), Chord::from_str("C").unwrap().inversion(1));
+        assert_eq!(Chord::from_str("C").unwrap().inversion(3), None);
+        assert_eq!(chord.to_string(), "C/E");
+        assert_eq!(chord.close_voicing(3), vec![(Note::WhiteNote(WhiteNote::E), 3), (Note::WhiteNote(WhiteNote::G), 3), (Note::WhiteNote(WhiteNote::C), 4)]);
+        assert_eq!(chord.transpose_semitones(2).to_string(), "D/F#");
+
+        let chord = Chord::try_from_str("Am7/D").unwrap();
+        assert_eq!(chord.bass_note(), Note::WhiteNote(WhiteNote::D));
+        assert_eq!(chord.close_voicing(3)[0], (Note::WhiteNote(WhiteNote::D), 3));
+        assert_eq!(Chord::from_str("C6/9").unwrap().bass_note(), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(Chord::try_from_str("C/H"), Err(ChordParseError::InvalidRoot));
+    }
+
+    #[test]
+    fn test_all_symbols() {
+        let symbols = ChordQuality::all_symbols();
+        assert_eq!(symbols.len(), ChordQuality::iter().count());
+        assert!(ChordQuality::iter().all(|quality| symbols.iter().any(|(other, _)| *other == quality)));
+        let unique: HashSet<&str> = symbols.iter().map(|(_, symbol)| *symbol).collect();
+        assert_eq!(unique.len(), symbols.len());
+        assert!(symbols.contains(&(ChordQuality::HalfDiminished, "m7b5")));
+        for (quality, symbol) in symbols {
+            assert_eq!(Chord::from_str(&format!("C{}", symbol)).unwrap().quality(), &quality);
+        }
+    }
+
+    #[test]
+    fn test_figured_bass() {
+        let triad = Chord::from_str("C").unwrap();
+        assert_eq!(triad.figured_bass(0), Some("".to_string()));
+        assert_eq!(triad.figured_bass(1), Some("6".to_string()));
+        assert_eq!(triad.figured_bass(2), Some("6/4".to_string()));
+        assert_eq!(triad.figured_bass(3), None);
+        let seventh = Chord::from_str("G7").unwrap();
+        assert_eq!(seventh.figured_bass(0), Some("7".to_string()));
+        assert_eq!(seventh.figured_bass(1), Some("6/5".to_string()));
+        assert_eq!(seventh.figured_bass(2), Some("4/3".to_string()));
+        assert_eq!(seventh.figured_bass(3), Some("4/2".to_string()));
+
+        assert_eq!(Chord::from_str("C6").unwrap().figured_bass(0), None);
+        assert_eq!(Chord::from_str("Cadd9").unwrap().figured_bass(0), None);
+        assert_eq!(Chord::from_str("C6/9").unwrap().figured_bass(4), None);
+        assert_eq!(Chord::from_str("C7b9").unwrap().figured_bass(0), None);
+        let no_fifth = Chord::from_str("C7(no5)").unwrap();
+        assert_eq!(no_fifth.figured_bass(1), Some("6/5".to_string()));
+        assert_eq!(no_fifth.figured_bass(2), None);
+    }
+
+    #[test]
+    fn test_respell_for_key() {
+        let db_major = Scale::new(Note::Flat(WhiteNote::D), ScaleType::Major);
+        assert_eq!(Chord::from_str("Ab").unwrap().respell_for_key(&db_major).to_string(), "Ab");
+        assert_eq!(Chord::from_str("G#").unwrap().respell_for_key(&db_major).to_string(), "Ab");
+        assert_eq!(Chord::from_str("F#m").unwrap().respell_for_key(&db_major).to_string(), "Gbm");
+
+        let a_major = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Major);
+        assert_eq!(Chord::from_str("Ab").unwrap().respell_for_key(&a_major).to_string(), "G#");
+        assert_eq!(Chord::from_str("Bb7").unwrap().respell_for_key(&a_major).to_string(), "A#7");
+        assert_eq!(Chord::from_str("Db/Ab").unwrap().respell_for_key(&a_major).to_string(), "C#/G#");
+
+        let c_major = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(Chord::from_str("Ab").unwrap().respell_for_key(&c_major).to_string(), "Ab");
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        for quality in ChordQuality::iter() {
+            assert_eq!(ChordQuality::from_symbol(quality.symbol()), Some(quality.clone()));
+            for root in [Note::WhiteNote(WhiteNote::C), Note::Sharp(WhiteNote::F), Note::Flat(WhiteNote::B)] {
+                let chord = Chord::new(root, quality.clone());
+                let parsed = Chord::from_str(&chord.to_string()).unwrap();
+                assert_eq!(parsed, chord);
+                assert_eq!(parsed.to_string(), chord.to_string());
+            }
+        }
+        for symbol in ["G7b9#5", "Cmaj7(no5)", "Am7/G", "D/F#"] {
+            assert_eq!(Chord::from_str(symbol).unwrap().to_string(), symbol);
+        }
+        assert_eq!(ChordQuality::from_symbol("maj9"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        for symbol in ["C#m7", "Bb7b9", "Cmaj7(no5)", "Am7/G"] {
+            let chord = Chord::from_str(symbol).unwrap();
+            let json = serde_json::to_string(&chord).unwrap();
+            let parsed: Chord = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, chord);
+            assert_eq!(parsed.to_string(), symbol);
+        }
+        let custom = ChordQuality::Custom(vec![Interval::major_third(), Interval::augmented_fifth()]);
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(serde_json::from_str::<ChordQuality>(&json).unwrap(), custom);
+        assert!(serde_json::to_string(&Chord::from_str("F#").unwrap()).unwrap().contains("\"F#\""));
+    }
 }
@@ -0,0 +1,128 @@
+use crate::notes::*;
+use crate::chords::*;
+use crate::progressions::*;
+
+pub type Shape = [Option<u8>; 6];
+
+pub const STANDARD_TUNING: [Note; 6] = [
+    Note::WhiteNote(WhiteNote::E),
+    Note::WhiteNote(WhiteNote::A),
+    Note::WhiteNote(WhiteNote::D),
+    Note::WhiteNote(WhiteNote::G),
+    Note::WhiteNote(WhiteNote::B),
+    Note::WhiteNote(WhiteNote::E),
+];
+
+/// Note sounding on `string` (0 being the low E) at `fret` in standard tuning.
+pub fn fret_note(string: usize, fret: u8) -> Note {
+    STANDARD_TUNING[string].transpose_semitones((fret % 12) as i8)
+}
+
+pub fn sounding_notes(shape: &Shape) -> Vec<Note> {
+    shape.iter()
+        .enumerate()
+        .filter_map(|(string, fret)| fret.map(|fret| fret_note(string, fret)))
+        .collect()
+}
+
+/// Whether one hand can hold the shape: muted strings only on the bass side,
+/// at least four strings ringing, a four fret span and no more than four
+/// fingers, the index finger barring the lowest fret when no string is open.
+pub fn is_playable(shape: &Shape) -> bool {
+    let first_sounding = match shape.iter().position(|fret| fret.is_some()) {
+        Some(string) => string,
+        None => return false,
+    };
+    if shape[first_sounding..].iter().any(|fret| fret.is_none()) || first_sounding > 2 {
+        return false;
+    }
+    let fretted: Vec<u8> = shape.iter().flatten().copied().filter(|fret| *fret > 0).collect();
+    let (lowest, highest) = match (fretted.iter().min(), fretted.iter().max()) {
+        (Some(lowest), Some(highest)) => (*lowest, *highest),
+        _ => return true,
+    };
+    if highest - lowest > 3 {
+        return false;
+    }
+    let fingers = if fretted.len() < shape.len() - first_sounding {
+        fretted.len()
+    }
+    else {
+        1 + fretted.iter().filter(|fret| **fret > lowest).count()
+    };
+    fingers <= 4
+}
+
+/// Whether the strings fretted so far, from the low E up, can still be
+/// completed into a playable shape. Used to prune the search in
+/// `Chord::guitar_shapes`.
+pub fn is_playable_prefix(frets: &[Option<u8>]) -> bool {
+    let first_sounding = frets.iter().position(|fret| fret.is_some());
+    match first_sounding {
+        Some(string) if frets[string..].iter().any(|fret| fret.is_none()) || string > 2 => return false,
+        None if frets.len() > 3 => return false,
+        _ => {},
+    }
+    let fretted: Vec<u8> = frets.iter().flatten().copied().filter(|fret| *fret > 0).collect();
+    let (lowest, highest) = match (fretted.iter().min(), fretted.iter().max()) {
+        (Some(lowest), Some(highest)) => (*lowest, *highest),
+        _ => return true,
+    };
+    highest - lowest <= 3 && fretted.iter().filter(|fret| **fret > lowest).count() < 4
+}
+
+/// Chords to finger with a capo on `capo_fret` so that `chords` sound.
+pub fn capo_shapes(chords: &[Chord], capo_fret: u8) -> Vec<Chord> {
+    transpose_progression(chords, -((capo_fret % 12) as i8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fret_note() {
+        assert_eq!(fret_note(0, 0), Note::WhiteNote(WhiteNote::E));
+        assert_eq!(fret_note(1, 3), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(fret_note(4, 1), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(fret_note(5, 12), Note::WhiteNote(WhiteNote::E));
+    }
+
+    #[test]
+    fn test_is_playable() {
+        assert!(is_playable(&[None, Some(3), Some(2), Some(0), Some(1), Some(0)]));
+        assert!(is_playable(&[Some(1), Some(3), Some(3), Some(2), Some(1), Some(1)]));
+        assert!(!is_playable(&[Some(1), Some(3), Some(3), Some(2), Some(4), Some(1)]));
+        assert!(!is_playable(&[Some(0), Some(1), Some(6), Some(0), Some(0), Some(0)]));
+        assert!(!is_playable(&[Some(3), None, Some(0), Some(0), Some(0), Some(3)]));
+        assert!(!is_playable(&[None; 6]));
+    }
+
+    #[test]
+    fn test_is_playable_prefix() {
+        assert!(is_playable_prefix(&[]));
+        assert!(is_playable_prefix(&[None, None, None]));
+        assert!(!is_playable_prefix(&[None, None, None, None]));
+        assert!(!is_playable_prefix(&[None, Some(3), None]));
+        assert!(!is_playable_prefix(&[Some(1), Some(6)]));
+        assert!(!is_playable_prefix(&[Some(1), Some(2), Some(3), Some(4), Some(4)]));
+        assert!(is_playable_prefix(&[Some(1), Some(3), Some(3), Some(2), Some(1)]));
+    }
+
+    #[test]
+    fn test_capo_shapes() {
+        let chords = vec![
+            Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::DominantSeventh),
+        ];
+        assert_eq!(capo_shapes(&chords, 2), vec![
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh),
+        ]);
+        assert_eq!(capo_shapes(&chords, 0), chords);
+    }
+}
@@ -1,3 +1,10 @@
 pub mod notes;
 pub mod chords;
 pub mod scales;
+pub mod progressions;
+pub mod pitch_class_sets;
+pub mod guitar;
+pub mod abc;
+pub mod lilypond;
+pub mod rhythm;
+pub mod musicxml;
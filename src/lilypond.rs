@@ -0,0 +1,54 @@
+use crate::notes::*;
+
+pub fn note_to_lilypond(note: &Note) -> String {
+    let (white_note, suffix) = match note {
+        Note::WhiteNote(white_note) => (white_note, ""),
+        Note::Sharp(white_note) => (white_note, "is"),
+        Note::Flat(white_note) => (white_note, "es"),
+        Note::DoubleSharp(white_note) => (white_note, "isis"),
+        Note::DoubleFlat(white_note) => (white_note, "eses"),
+    };
+    format!("{}{}", Note::WhiteNote(white_note.clone()).to_string().to_lowercase(), suffix)
+}
+
+/// Absolute Lilypond pitch, where octave 3 is unmarked ("c") and each octave
+/// above or below adds a "'" or a ",".
+pub fn pitch_to_lilypond(note: &Note, octave: i8) -> String {
+    let markers = if octave >= 3 {
+        "'".repeat((octave - 3) as usize)
+    }
+    else {
+        ",".repeat((3 - octave as i16) as usize)
+    };
+    format!("{}{}", note_to_lilypond(note), markers)
+}
+
+pub fn pitches_to_lilypond(pitches: &[(Note, i8)]) -> String {
+    pitches.iter().map(|(note, octave)| pitch_to_lilypond(note, *octave)).collect::<Vec<String>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scales::*;
+
+    #[test]
+    fn test_note_to_lilypond() {
+        assert_eq!(note_to_lilypond(&Note::Sharp(WhiteNote::C)), "cis");
+        assert_eq!(note_to_lilypond(&Note::Flat(WhiteNote::B)), "bes");
+        assert_eq!(note_to_lilypond(&Note::Flat(WhiteNote::E)), "ees");
+        assert_eq!(note_to_lilypond(&Note::DoubleSharp(WhiteNote::F)), "fisis");
+        assert_eq!(note_to_lilypond(&Note::WhiteNote(WhiteNote::G)), "g");
+    }
+
+    #[test]
+    fn test_pitches_to_lilypond() {
+        assert_eq!(pitch_to_lilypond(&Note::WhiteNote(WhiteNote::C), 4), "c'");
+        assert_eq!(pitch_to_lilypond(&Note::WhiteNote(WhiteNote::C), 3), "c");
+        assert_eq!(pitch_to_lilypond(&Note::WhiteNote(WhiteNote::A), 1), "a,,");
+        assert_eq!(pitch_to_lilypond(&Note::WhiteNote(WhiteNote::C), -128), format!("c{}", ",".repeat(131)));
+        assert_eq!(pitch_to_lilypond(&Note::WhiteNote(WhiteNote::C), 127), format!("c{}", "'".repeat(124)));
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Major);
+        assert_eq!(pitches_to_lilypond(&scale.pitches_over_octaves(1, 4).unwrap()), "d' e' fis' g' a' b' cis'' d''");
+    }
+}
@@ -1,29 +1,338 @@
-use clap::{arg, Command};
+use clap::{arg, value_parser, Arg, ArgMatches, Command};
 use cameron::notes::*;
 use cameron::chords::*;
 use cameron::scales::*;
+use cameron::progressions::*;
+use cameron::abc::*;
+use cameron::lilypond::*;
+use cameron::musicxml::*;
 
-fn main() {
-    let matches = Command::new("cameron")
+fn midi_numbers(notes: &[Note], octave: i8) -> Option<Vec<u8>> {
+    let mut numbers: Vec<u8> = Vec::new();
+    for note in notes {
+        let mut number = note.to_midi(octave)?;
+        while let Some(previous) = numbers.last() {
+            if number > *previous {
+                break;
+            }
+            number = number.checked_add(12).filter(|n| *n <= 127)?;
+        }
+        numbers.push(number);
+    }
+    Some(numbers)
+}
+
+fn ascending_pitches(notes: &[Note], octave: i8) -> Option<Vec<(Note, i8)>> {
+    let mut pitches: Vec<(Note, i8)> = Vec::new();
+    for note in notes {
+        let mut pitch_octave = octave;
+        if let Some((previous, previous_octave)) = pitches.last() {
+            pitch_octave = *previous_octave;
+            if note.to_midi(pitch_octave)? <= previous.to_midi(*previous_octave)? {
+                pitch_octave = pitch_octave.checked_add(1)?;
+            }
+        }
+        note.to_midi(pitch_octave)?;
+        pitches.push((note.clone(), pitch_octave));
+    }
+    Some(pitches)
+}
+
+fn parse_key(s: &str) -> Option<Scale> {
+    Scale::from_str(s).or_else(|| Note::from_str(s).map(|root| Scale::new(root, ScaleType::Major)))
+}
+
+fn note_names(notes: &[Note], highlight: &[Note]) -> Vec<String> {
+    notes.iter().map(|n| {
+        if highlight.contains(n) {
+            format!("\x1b[1;32m{}\x1b[0m", n)
+        }
+        else {
+            n.to_string()
+        }
+    }).collect()
+}
+
+fn print_notes(notes: &[Note], matches: &ArgMatches, descending: bool, highlight: &[Note]) {
+    let notes: Vec<Note> = match matches.get_one::<String>("spelling").map(|spelling| spelling.as_str()) {
+        Some("sharps") => notes.iter().map(|note| note.respell(true)).collect(),
+        Some("flats") => notes.iter().map(|note| note.respell(false)).collect(),
+        _ => notes.to_vec(),
+    };
+    if matches.get_flag("midi") {
+        let octave = *matches.get_one::<i8>("octave").unwrap();
+        if let Some(mut numbers) = midi_numbers(&notes, octave) {
+            if descending {
+                numbers.reverse();
+            }
+            println!("{}", numbers.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "));
+        }
+        else {
+            println!("Invalid octave provided.");
+        }
+    }
+    else if let Some(format @ ("abc" | "lilypond" | "musicxml")) = matches.get_one::<String>("format").map(|format| format.as_str()) {
+        if let Some(mut pitches) = ascending_pitches(&notes, *matches.get_one::<i8>("octave").unwrap()) {
+            if descending {
+                pitches.reverse();
+            }
+            match format {
+                "abc" => println!("{}", pitches_to_abc(&pitches)),
+                "lilypond" => println!("{}", pitches_to_lilypond(&pitches)),
+                _ => println!("{}", pitches_to_musicxml(&pitches)),
+            }
+        }
+        else {
+            println!("Invalid octave provided.");
+        }
+    }
+    else {
+        let mut names = note_names(&notes, highlight);
+        if descending {
+            names.reverse();
+        }
+        println!("{}", names.join(" "));
+    }
+}
+
+fn cli() -> Command {
+    Command::new("cameron")
         .about("A multi-purpose cli music theory tool")
         .subcommand(
             Command::new("scale")
                 .about("Displays he notes of a scale")
                 .arg(arg!([SCALE]))
+                .arg(arg!(--descending "Displays the scale from high to low"))
+                .arg(arg!(--"with-octave" "Repeats the tonic an octave higher at the end"))
+                .arg(arg!(--highlight <CHORD> "Colors the notes of the scale belonging to a chord"))
+                .arg(arg!(--spelling <SPELLING> "Spells every note with sharps or flats")
+                    .value_parser(["sharps", "flats"]))
+                .arg(arg!(--format <FORMAT> "Notation used to display the notes")
+                    .value_parser(["names", "abc", "lilypond", "musicxml"])
+                    .default_value("names"))
+                .arg(arg!(--midi "Displays MIDI note numbers instead of note names"))
+                .arg(arg!(--octave <OCTAVE> "Octave of the root in MIDI mode")
+                    .value_parser(value_parser!(i8))
+                    .allow_negative_numbers(true)
+                    .default_value("4"))
         )
         .subcommand(
             Command::new("chord")
                 .about("Displays the notes of a chord")
                 .arg(arg!([CHORD]))
+                .arg(arg!(--transpose <INTERVAL> "Transposes the chord up by an interval such as P5 or m3"))
+                .arg(arg!(--symbol "Displays the chord symbol instead of its notes"))
+                .arg(arg!(--spelling <SPELLING> "Spells every note with sharps or flats")
+                    .value_parser(["sharps", "flats"]))
+                .arg(arg!(--format <FORMAT> "Notation used to display the notes")
+                    .value_parser(["names", "abc", "lilypond", "musicxml"])
+                    .default_value("names"))
+                .arg(arg!(--midi "Displays MIDI note numbers instead of note names"))
+                .arg(arg!(--octave <OCTAVE> "Octave of the root in MIDI mode")
+                    .value_parser(value_parser!(i8))
+                    .allow_negative_numbers(true)
+                    .default_value("4"))
+        )
+        .subcommand(
+            Command::new("progression")
+                .about("Displays the chords of a progression")
+                .arg(arg!([PROGRESSION]))
+                .arg(arg!(--key <KEY> "Key of the roman numerals").default_value("C"))
+        )
+        .subcommand(
+            Command::new("harmonize")
+                .about("Displays the diatonic chords of a key")
+                .arg(arg!([KEY]))
+        )
+        .subcommand(
+            Command::new("frequencies")
+                .about("Displays the frequencies of the notes of a scale")
+                .arg(arg!([SCALE]))
+                .arg(arg!(--octave <OCTAVE> "Octave of the root")
+                    .value_parser(value_parser!(i8))
+                    .allow_negative_numbers(true)
+                    .default_value("4"))
+                .arg(arg!(--reference <FREQUENCY> "Frequency of A4 in Hz")
+                    .value_parser(value_parser!(f64))
+                    .allow_negative_numbers(true)
+                    .default_value("440"))
         )
-        .get_matches();
+        .subcommand(
+            Command::new("stack")
+                .about("Displays the notes of intervals stacked on a root")
+                .arg(arg!([ROOT]))
+                .arg(arg!([INTERVALS]...))
+        )
+        .subcommand(
+            Command::new("identify")
+                .about("Names the chords made of the given notes or MIDI note numbers")
+                .arg(arg!([NOTES]...))
+        )
+        .subcommand(
+            Command::new("repl")
+                .about("Reads commands from standard input until the end of input")
+        )
+        .subcommand(
+            Command::new("completions")
+                .about("Prints a shell completion script")
+                .hide(true)
+                .arg(arg!([SHELL]).value_parser(["bash", "fish", "zsh"]))
+        )
+}
+
+fn visible_subcommands(command: &Command) -> Vec<&Command> {
+    command.get_subcommands().filter(|subcommand| !subcommand.is_hide_set()).collect()
+}
+
+fn long_flags(command: &Command) -> Vec<String> {
+    command.get_arguments().filter_map(|arg| arg.get_long()).map(|long| format!("--{}", long)).collect()
+}
+
+fn bash_completions(command: &Command) -> String {
+    let name = command.get_name();
+    let subcommands = visible_subcommands(command);
+    let mut script = format!("_{}() {{\n", name);
+    script.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    script.push_str("    if [ \"$COMP_CWORD\" -eq 1 ]; then\n");
+    script.push_str(&format!(
+        "        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+        subcommands.iter().map(|subcommand| subcommand.get_name()).collect::<Vec<&str>>().join(" ")
+    ));
+    script.push_str("        return\n    fi\n    case \"${COMP_WORDS[1]}\" in\n");
+    for subcommand in subcommands {
+        script.push_str(&format!(
+            "        {}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")) ;;\n",
+            subcommand.get_name(),
+            long_flags(subcommand).join(" ")
+        ));
+    }
+    script.push_str(&format!("    esac\n}}\ncomplete -F _{} {}\n", name, name));
+    script
+}
+
+/// Escapes text for a fish single-quoted string.
+fn fish_quote(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('\'', "\\'")
+}
 
+fn fish_completions(command: &Command) -> String {
+    let name = command.get_name();
+    let mut script = String::new();
+    for subcommand in visible_subcommands(command) {
+        script.push_str(&format!(
+            "complete -c {} -n \"__fish_use_subcommand\" -f -a {} -d '{}'\n",
+            name,
+            subcommand.get_name(),
+            fish_quote(&subcommand.get_about().map(|about| about.to_string()).unwrap_or_default())
+        ));
+        for arg in subcommand.get_arguments() {
+            if let Some(long) = arg.get_long() {
+                script.push_str(&format!(
+                    "complete -c {} -n \"__fish_seen_subcommand_from {}\" -l {} -d '{}'\n",
+                    name,
+                    subcommand.get_name(),
+                    long,
+                    fish_quote(&arg.get_help().map(|help| help.to_string()).unwrap_or_default())
+                ));
+            }
+        }
+    }
+    script
+}
+
+/// Escapes text for a zsh single-quoted string.
+fn zsh_quote(text: &str) -> String {
+    text.replace('\'', "'\\''")
+}
+
+fn zsh_completions(command: &Command) -> String {
+    let name = command.get_name();
+    let subcommands = visible_subcommands(command);
+    let mut script = format!("#compdef {}\n\n_{}() {{\n    local -a subcommands\n    subcommands=(\n", name, name);
+    for subcommand in &subcommands {
+        script.push_str(&format!(
+            "        '{}:{}'\n",
+            subcommand.get_name(),
+            zsh_quote(&subcommand.get_about().map(|about| about.to_string()).unwrap_or_default())
+        ));
+    }
+    script.push_str("    )\n    if (( CURRENT == 2 )); then\n        _describe 'command' subcommands\n        return\n    fi\n");
+    script.push_str("    case $words[2] in\n");
+    for subcommand in subcommands {
+        let flags: Vec<String> = subcommand.get_arguments()
+            .filter_map(|arg| arg.get_long().map(|long| {
+                let help = arg.get_help().map(|help| help.to_string()).unwrap_or_default();
+                format!("'--{}[{}]'", long, zsh_quote(&help.replace('[', "\\[").replace(']', "\\]")))
+            }))
+            .collect();
+        if !flags.is_empty() {
+            script.push_str(&format!("        {}) _arguments {} ;;\n", subcommand.get_name(), flags.join(" ")));
+        }
+    }
+    script.push_str(&format!("    esac\n}}\n\ncompdef _{} {}\n", name, name));
+    script
+}
+
+fn repl_tokens(line: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = line.split_whitespace().map(|token| token.to_string()).collect();
+    let single_positional = tokens.first()
+        .and_then(|name| cli().find_subcommand(name).map(|subcommand| {
+            let positionals: Vec<&Arg> = subcommand.get_positionals().collect();
+            positionals.len() == 1 && positionals[0].get_num_args().is_none_or(|range| range.max_values() == 1)
+        }))
+        .unwrap_or(false);
+    if single_positional {
+        let end = tokens.iter().skip(1).position(|token| token.starts_with("--")).map_or(tokens.len(), |i| i + 1);
+        if end > 2 {
+            let positional = tokens[1..end].join(" ");
+            tokens.splice(1..end, [positional]);
+        }
+    }
+    tokens
+}
+
+fn repl() {
+    for line in std::io::stdin().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match cli().no_binary_name(true).try_get_matches_from(repl_tokens(&line)) {
+            Ok(matches) => run(&matches),
+            Err(error) => print!("{}", error),
+        }
+    }
+}
+
+fn main() {
+    run(&cli().get_matches());
+}
+
+fn run(matches: &ArgMatches) {
     match matches.subcommand() {
         Some(("scale", scale_matches)) => {
             if let Some(scale) = scale_matches.get_one::<String>("SCALE") {
                 if let Some(scale) = Scale::from_str(scale) {
-                    let notes = scale.get_notes();
-                    println!("{}", notes.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "));
+                    let notes = if scale_matches.get_flag("with-octave") {
+                        scale.get_notes_with_octave_tonic()
+                    }
+                    else {
+                        scale.get_notes()
+                    };
+                    let highlight = match scale_matches.get_one::<String>("highlight") {
+                        Some(chord) => match Chord::try_from_str(chord) {
+                            Ok(chord) => chord.get_notes(),
+                            Err(error) => {
+                                println!("Invalid chord provided: {}.", error);
+                                return;
+                            }
+                        },
+                        None => Vec::new(),
+                    };
+                    print_notes(&notes, scale_matches, scale_matches.get_flag("descending"), &highlight);
                 }
                 else {
                     println!("Invalid scale provided.");
@@ -35,18 +344,160 @@ fn main() {
         }
         Some(("chord", chord_matches)) => {
             if let Some(chord) = chord_matches.get_one::<String>("CHORD") {
-                if let Some(chord) = Chord::from_str(chord) {
-                    let notes = chord.get_notes();
-                    println!("{}", notes.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "));
+                match Chord::try_from_str(chord) {
+                    Ok(chord) => {
+                        let chord = match chord_matches.get_one::<String>("transpose") {
+                            Some(interval) => match Interval::from_str(interval) {
+                                Some(interval) => chord.transpose(interval),
+                                None => {
+                                    println!("Invalid interval provided.");
+                                    return;
+                                }
+                            },
+                            None => chord,
+                        };
+                        if chord_matches.get_flag("symbol") {
+                            println!("{}", chord);
+                        }
+                        else {
+                            print_notes(&chord.get_notes(), chord_matches, false, &[]);
+                        }
+                    },
+                    Err(error) => println!("Invalid chord provided: {}.", error),
+                }
+            }
+            else {
+                println!("No chord provided.");
+            }
+        }
+        Some(("progression", progression_matches)) => {
+            if let Some(progression) = progression_matches.get_one::<String>("PROGRESSION") {
+                let key = progression_matches.get_one::<String>("key").unwrap();
+                if let Some(key) = parse_key(key) {
+                    if let Some(chords) = parse_progression(progression, &key) {
+                        for chord in chords {
+                            println!("{}: {}", chord, chord.get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "));
+                        }
+                    }
+                    else {
+                        println!("Invalid progression provided.");
+                    }
                 }
                 else {
-                    println!("Invalid chord provided.");
+                    println!("Invalid key provided.");
                 }
             }
             else {
-                println!("No chord provided.");
+                println!("No progression provided.");
+            }
+        }
+        Some(("harmonize", harmonize_matches)) => {
+            if let Some(key) = harmonize_matches.get_one::<String>("KEY") {
+                if let Some(key) = parse_key(key) {
+                    match key.diatonic_chords() {
+                        Some(chords) => {
+                            for (degree, chord) in chords.iter().enumerate() {
+                                let degree = degree as u8 + 1;
+                                println!("{}: {}", chord.roman_numeral(degree).unwrap_or_else(|| degree.to_string()), chord);
+                            }
+                        },
+                        None => println!("This scale cannot be harmonized in thirds."),
+                    }
+                }
+                else {
+                    println!("Invalid key provided.");
+                }
+            }
+            else {
+                println!("No key provided.");
+            }
+        }
+        Some(("frequencies", frequencies_matches)) => {
+            if let Some(scale) = frequencies_matches.get_one::<String>("SCALE") {
+                if let Some(scale) = Scale::from_str(scale) {
+                    let octave = *frequencies_matches.get_one::<i8>("octave").unwrap();
+                    let reference = *frequencies_matches.get_one::<f64>("reference").unwrap();
+                    let pitches = scale.pitches_over_octaves(1, octave)
+                        .map(|pitches| pitches[..pitches.len() - 1].to_vec())
+                        .filter(|pitches| pitches.iter().all(|(note, octave)| note.to_midi(*octave).is_some()));
+                    if !(reference.is_finite() && reference > 0.0) {
+                        println!("Invalid reference frequency provided.");
+                    }
+                    else if let Some(pitches) = pitches {
+                        for (note, octave) in pitches.iter() {
+                            println!("{}{}  {:.2}", note, octave, note.frequency(*octave, reference));
+                        }
+                    }
+                    else {
+                        println!("Invalid octave provided.");
+                    }
+                }
+                else {
+                    println!("Invalid scale provided.");
+                }
+            }
+            else {
+                println!("No scale provided.");
+            }
+        }
+        Some(("stack", stack_matches)) => {
+            if let Some(root) = stack_matches.get_one::<String>("ROOT") {
+                if let Some(root) = Note::from_str(root) {
+                    let intervals: Option<Vec<Interval>> = stack_matches.get_many::<String>("INTERVALS")
+                        .unwrap_or_default()
+                        .map(|interval| Interval::from_str(interval))
+                        .collect();
+                    if let Some(intervals) = intervals {
+                        let mut notes = vec![root.clone()];
+                        notes.extend(intervals.into_iter().map(|interval| root.up(interval)));
+                        println!("{}", notes.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "));
+                    }
+                    else {
+                        println!("Invalid interval provided.");
+                    }
+                }
+                else {
+                    println!("Invalid root provided.");
+                }
+            }
+            else {
+                println!("No root provided.");
+            }
+        }
+        Some(("identify", identify_matches)) => {
+            let notes: Option<Vec<Note>> = identify_matches.get_many::<String>("NOTES")
+                .unwrap_or_default()
+                .map(|note| match note.parse::<u8>() {
+                    Ok(midi) => Note::from_midi(midi).map(|(note, _)| note),
+                    Err(_) => Note::from_str(note),
+                })
+                .collect();
+            match notes {
+                Some(notes) if !notes.is_empty() => {
+                    let chords: Vec<Chord> = Chord::reverse_lookup_ranked(&notes).into_iter()
+                        .filter(|(_, score)| *score == 1.0)
+                        .map(|(chord, _)| chord)
+                        .collect();
+                    if chords.is_empty() {
+                        println!("No chord found.");
+                    }
+                    for chord in chords {
+                        println!("{}", chord);
+                    }
+                }
+                Some(_) => println!("No notes provided."),
+                None => println!("Invalid note provided."),
+            }
+        }
+        Some(("completions", completions_matches)) => {
+            match completions_matches.get_one::<String>("SHELL").map(|shell| shell.as_str()) {
+                Some("bash") => print!("{}", bash_completions(&cli())),
+                Some("fish") => print!("{}", fish_completions(&cli())),
+                Some("zsh") => print!("{}", zsh_completions(&cli())),
+                _ => println!("No shell provided."),
             }
         }
+        Some(("repl", _)) => repl(),
         _ => {
             println!("No command provided.");
         }
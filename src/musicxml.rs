@@ -0,0 +1,42 @@
+use crate::notes::*;
+
+/// MusicXML `<pitch>` element, with `<alter>` left out for natural notes.
+pub fn pitch_to_musicxml(note: &Note, octave: i8) -> String {
+    let (white_note, alter) = match note {
+        Note::WhiteNote(white_note) => (white_note, 0),
+        Note::Sharp(white_note) => (white_note, 1),
+        Note::Flat(white_note) => (white_note, -1),
+        Note::DoubleSharp(white_note) => (white_note, 2),
+        Note::DoubleFlat(white_note) => (white_note, -2),
+    };
+    let alter = if alter == 0 {
+        String::new()
+    }
+    else {
+        format!("<alter>{}</alter>", alter)
+    };
+    format!("<pitch><step>{}</step>{}<octave>{}</octave></pitch>", Note::WhiteNote(white_note.clone()), alter, octave)
+}
+
+pub fn pitches_to_musicxml(pitches: &[(Note, i8)]) -> String {
+    pitches.iter().map(|(note, octave)| pitch_to_musicxml(note, *octave)).collect::<Vec<String>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pitch_to_musicxml() {
+        assert_eq!(pitch_to_musicxml(&Note::Sharp(WhiteNote::C), 4), "<pitch><step>C</step><alter>1</alter><octave>4</octave></pitch>");
+        assert_eq!(pitch_to_musicxml(&Note::Flat(WhiteNote::B), 3), "<pitch><step>B</step><alter>-1</alter><octave>3</octave></pitch>");
+        assert_eq!(pitch_to_musicxml(&Note::WhiteNote(WhiteNote::G), 5), "<pitch><step>G</step><octave>5</octave></pitch>");
+        assert_eq!(pitch_to_musicxml(&Note::DoubleFlat(WhiteNote::E), 4), "<pitch><step>E</step><alter>-2</alter><octave>4</octave></pitch>");
+    }
+
+    #[test]
+    fn test_pitches_to_musicxml() {
+        let pitches = [(Note::WhiteNote(WhiteNote::B), 3), (Note::WhiteNote(WhiteNote::C), 4)];
+        assert_eq!(pitches_to_musicxml(&pitches), "<pitch><step>B</step><octave>3</octave></pitch>\n<pitch><step>C</step><octave>4</octave></pitch>");
+    }
+}
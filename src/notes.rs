@@ -1,7 +1,11 @@
+use std::collections::HashSet;
 use std::fmt;
+use regex::Regex;
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-#[derive(Debug, Clone, EnumIter, Hash)]
+#[derive(Debug, Clone, PartialEq, EnumIter, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WhiteNote { C, D, E, F, G, A, B }
 
 #[derive(Debug, Clone)]
@@ -9,9 +13,12 @@ pub enum Note {
     WhiteNote(WhiteNote),
     Sharp(WhiteNote),
     Flat(WhiteNote),
+    DoubleSharp(WhiteNote),
+    DoubleFlat(WhiteNote),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IntervalQuality {
     Perfect,
     Major,
@@ -20,41 +27,318 @@ pub enum IntervalQuality {
     Diminished,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Interval {
     quality: IntervalQuality,
     number: u8,
 }
 
+#[derive(Debug, PartialEq)]
+pub enum IntervalError {
+    InvalidInterval(IntervalQuality, u8),
+    TooManyAccidentals(IntervalQuality, u8),
+}
+
+impl fmt::Display for IntervalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IntervalError::InvalidInterval(quality, number) => write!(f, "Invalid interval: {:?} {}", quality, number),
+            IntervalError::TooManyAccidentals(quality, number) =>
+                write!(f, "{:?} {} needs more than two accidentals from this note", quality, number),
+        }
+    }
+}
+
+impl std::error::Error for IntervalError {}
+
+/// Short name such as "M3" or "P5", which `Interval::from_str` reads back.
+impl fmt::Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let quality = match self.quality {
+            IntervalQuality::Perfect => "P",
+            IntervalQuality::Major => "M",
+            IntervalQuality::Minor => "m",
+            IntervalQuality::Augmented => "A",
+            IntervalQuality::Diminished => "d",
+        };
+        write!(f, "{}{}", quality, self.number)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum MidiError {
+    OutOfRange(u8),
+    InvalidRange(u8, u8),
+}
+
+impl fmt::Display for MidiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MidiError::OutOfRange(midi) => write!(f, "MIDI number out of range: {}", midi),
+            MidiError::InvalidRange(low, high) => write!(f, "Invalid MIDI range: {} to {}", low, high),
+        }
+    }
+}
+
+impl std::error::Error for MidiError {}
+
 impl Interval {
     pub fn new(quality: IntervalQuality, number: u8) -> Interval {
         Interval { quality, number }
     }
 
-    fn get_number_semitones(&self) -> u8 {
+    pub fn quality(&self) -> &IntervalQuality {
+        &self.quality
+    }
+
+    pub fn number(&self) -> u8 {
+        self.number
+    }
+
+    pub fn perfect_unison() -> Interval {
+        Interval::new(IntervalQuality::Perfect, 1)
+    }
+
+    pub fn augmented_unison() -> Interval {
+        Interval::new(IntervalQuality::Augmented, 1)
+    }
+
+    pub fn minor_second() -> Interval {
+        Interval::new(IntervalQuality::Minor, 2)
+    }
+
+    pub fn major_second() -> Interval {
+        Interval::new(IntervalQuality::Major, 2)
+    }
+
+    pub fn augmented_second() -> Interval {
+        Interval::new(IntervalQuality::Augmented, 2)
+    }
+
+    pub fn minor_third() -> Interval {
+        Interval::new(IntervalQuality::Minor, 3)
+    }
+
+    pub fn major_third() -> Interval {
+        Interval::new(IntervalQuality::Major, 3)
+    }
+
+    pub fn perfect_fourth() -> Interval {
+        Interval::new(IntervalQuality::Perfect, 4)
+    }
+
+    pub fn augmented_fourth() -> Interval {
+        Interval::new(IntervalQuality::Augmented, 4)
+    }
+
+    pub fn diminished_fifth() -> Interval {
+        Interval::new(IntervalQuality::Diminished, 5)
+    }
+
+    pub fn perfect_fifth() -> Interval {
+        Interval::new(IntervalQuality::Perfect, 5)
+    }
+
+    pub fn augmented_fifth() -> Interval {
+        Interval::new(IntervalQuality::Augmented, 5)
+    }
+
+    pub fn minor_sixth() -> Interval {
+        Interval::new(IntervalQuality::Minor, 6)
+    }
+
+    pub fn major_sixth() -> Interval {
+        Interval::new(IntervalQuality::Major, 6)
+    }
+
+    pub fn diminished_seventh() -> Interval {
+        Interval::new(IntervalQuality::Diminished, 7)
+    }
+
+    pub fn minor_seventh() -> Interval {
+        Interval::new(IntervalQuality::Minor, 7)
+    }
+
+    pub fn major_seventh() -> Interval {
+        Interval::new(IntervalQuality::Major, 7)
+    }
+
+    pub fn octave() -> Interval {
+        Interval::new(IntervalQuality::Perfect, 8)
+    }
+
+    /// Builds an interval only if the quality/number combination exists:
+    /// perfect unisons, fourths, fifths and octaves; major and minor seconds,
+    /// thirds, sixths and sevenths; augmented intervals from the unison to the
+    /// octave; diminished intervals from the second to the octave. Compound
+    /// intervals follow the rule of the simple interval they extend.
+    pub fn try_new(quality: IntervalQuality, number: u8) -> Option<Interval> {
+        let simple_number = if number > 8 { (number - 1) % 7 + 1 } else { number };
+        let valid = match quality {
+            IntervalQuality::Perfect => matches!(simple_number, 1 | 4 | 5 | 8),
+            IntervalQuality::Major | IntervalQuality::Minor => matches!(simple_number, 2 | 3 | 6 | 7),
+            IntervalQuality::Augmented => matches!(simple_number, 1..=8),
+            IntervalQuality::Diminished => matches!(simple_number, 2..=8),
+        };
+        let interval = Interval { quality, number };
+        if valid && interval.get_number_semitones().is_ok() { Some(interval) } else { None }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<Interval> {
+        let re = Regex::new(r"^(P|M|m|A|d|perfect|major|minor|augmented|diminished)\s*(\d+)$").unwrap();
+        let caps = re.captures(s.trim())?;
+        let quality = match caps.get(1)?.as_str() {
+            "P" | "perfect" => IntervalQuality::Perfect,
+            "M" | "major" => IntervalQuality::Major,
+            "m" | "minor" => IntervalQuality::Minor,
+            "A" | "augmented" => IntervalQuality::Augmented,
+            "d" | "diminished" => IntervalQuality::Diminished,
+            _ => return None,
+        };
+        let number = caps.get(2)?.as_str().parse().ok()?;
+        Interval::try_new(quality, number)
+    }
+
+    fn from_number_and_semitones(number: u8, semitones: i16) -> Option<Interval> {
+        let simple_number = number.checked_sub(1)? % 7 + 1;
+        let simple_semitones = semitones - 12 * ((number - 1) / 7) as i16;
+        let perfect = matches!(simple_number, 1 | 4 | 5);
+        let base = if perfect {
+            Interval::new(IntervalQuality::Perfect, simple_number)
+        }
+        else {
+            Interval::new(IntervalQuality::Major, simple_number)
+        };
+        let quality = match (perfect, simple_semitones - base.get_number_semitones().ok()? as i16) {
+            (true, 0) => IntervalQuality::Perfect,
+            (false, 0) => IntervalQuality::Major,
+            (false, -1) => IntervalQuality::Minor,
+            (_, 1) => IntervalQuality::Augmented,
+            (true, -1) | (false, -2) => IntervalQuality::Diminished,
+            _ => return None,
+        };
+        Some(Interval::new(quality, number))
+    }
+
+    pub fn between(low: &Note, high: &Note) -> Option<Interval> {
+        let number = low.generic_interval(high);
+        let semitones = low.semitone_distance(high) as i16;
+        Interval::from_number_and_semitones(number, semitones)
+            .or_else(|| Interval::from_number_and_semitones(number, semitones + 12))
+            .or_else(|| Interval::from_number_and_semitones(number, semitones - 12))
+    }
+
+    /// Like `between`, but with octaves, so that wide intervals are compound:
+    /// C4 to E5 is a major tenth.
+    pub fn between_pitches(low: (&Note, i8), high: (&Note, i8)) -> Option<Interval> {
+        let (low_note, low_octave) = low;
+        let (high_note, high_octave) = high;
+        let steps = 7 * (high_octave as i16 - low_octave as i16)
+            + high_note.get_white_note().get_index() as i16 - low_note.get_white_note().get_index() as i16;
+        let semitones = high_note.get_midi_number(high_octave) - low_note.get_midi_number(low_octave);
+        if steps < 0 || semitones < 0 {
+            return None;
+        }
+        Interval::from_number_and_semitones(u8::try_from(steps + 1).ok()?, semitones)
+    }
+
+    /// Stacks `other` on top of this interval, e.g. a major third and a minor
+    /// third make a perfect fifth. Returns `None` when the sum has no quality
+    /// in `IntervalQuality`, such as a doubly augmented interval.
+    pub fn try_add(&self, other: &Interval) -> Option<Interval> {
+        if self.number == 0 || other.number == 0 {
+            return None;
+        }
+        let number = self.number.checked_add(other.number)?.checked_sub(1)?;
+        let semitones = self.get_number_semitones().ok()?.checked_add(other.get_number_semitones().ok()?)?;
+        Interval::from_number_and_semitones(number, semitones as i16)
+    }
+
+    /// Same as `try_add`, but panics if the sum is invalid.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(&self, other: &Interval) -> Interval {
+        self.try_add(other).expect("Invalid interval")
+    }
+
+    pub fn invert(&self) -> Interval {
+        let quality = match self.quality {
+            IntervalQuality::Perfect => IntervalQuality::Perfect,
+            IntervalQuality::Major => IntervalQuality::Minor,
+            IntervalQuality::Minor => IntervalQuality::Major,
+            IntervalQuality::Augmented => IntervalQuality::Diminished,
+            IntervalQuality::Diminished => IntervalQuality::Augmented,
+        };
+        let simple_number = if self.number > 8 { (self.number - 1) % 7 + 1 } else { self.number };
+        Interval::new(quality, 9 - simple_number)
+    }
+
+    fn get_number_semitones(&self) -> Result<u8, IntervalError> {
+        let invalid = || IntervalError::InvalidInterval(self.quality.clone(), self.number);
+        if self.number > 8 {
+            let octaves = (self.number - 1) / 7;
+            return Interval::new(self.quality.clone(), (self.number - 1) % 7 + 1).get_number_semitones()
+                .ok().and_then(|n| n.checked_add(12u8.checked_mul(octaves)?)).ok_or_else(invalid);
+        }
         match self.quality {
             IntervalQuality::Perfect => match self.number {
-                1 => 0,
-                4 => 5,
-                5 => 7,
-                8 => 12,
-                _ => panic!("Invalid interval"),
+                1 => Ok(0),
+                4 => Ok(5),
+                5 => Ok(7),
+                8 => Ok(12),
+                _ => Err(invalid()),
             },
             IntervalQuality::Major => match self.number {
-                2 => 2,
-                3 => 4,
-                6 => 9,
-                7 => 11,
-                _ => panic!("Invalid interval"),
+                2 => Ok(2),
+                3 => Ok(4),
+                6 => Ok(9),
+                7 => Ok(11),
+                _ => Err(invalid()),
             },
-            IntervalQuality::Minor => Interval::new(IntervalQuality::Major, self.number).get_number_semitones() - 1,
-            IntervalQuality::Augmented => Interval::new(IntervalQuality::Perfect, self.number).get_number_semitones() + 1,
-            IntervalQuality::Diminished => Interval::new(IntervalQuality::Perfect, self.number).get_number_semitones() - 1,
+            IntervalQuality::Minor => Interval::new(IntervalQuality::Major, self.number).get_number_semitones()
+                .map(|n| n - 1).map_err(|_| invalid()),
+            IntervalQuality::Augmented => Interval::new(IntervalQuality::Perfect, self.number).get_number_semitones()
+                .or_else(|_| Interval::new(IntervalQuality::Major, self.number).get_number_semitones())
+                .map(|n| n + 1).map_err(|_| invalid()),
+            IntervalQuality::Diminished => Interval::new(IntervalQuality::Perfect, self.number).get_number_semitones()
+                .or_else(|_| Interval::new(IntervalQuality::Minor, self.number).get_number_semitones())
+                .ok().and_then(|n| n.checked_sub(1)).ok_or_else(invalid),
         }
     }
 }
 
+/// Notes serialize as their display string, e.g. "C#", so files stay readable.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Note {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Note {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Note, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Note::from_str(&s).ok_or_else(|| serde::de::Error::custom(format!("invalid note: {}", s)))
+    }
+}
+
+impl fmt::Display for WhiteNote {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
 impl WhiteNote {
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &str) -> Option<WhiteNote> {
+        match Note::from_str(s)? {
+            Note::WhiteNote(white_note) => Some(white_note),
+            _ => None,
+        }
+    }
+
     fn get_index(&self) -> u8 {
         match self {
             WhiteNote::C => 0,
@@ -92,18 +376,10 @@ impl fmt::Display for Note {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Note::WhiteNote(white_note) => write!(f, "{:?}", white_note),
-            Note::Sharp(white_note) =>
-                match white_note {
-                    WhiteNote::B => write!(f, "C"),
-                    WhiteNote::E => write!(f, "F"),
-                    _ => write!(f, "{:?}#", white_note),
-                }
-            Note::Flat(white_note) =>
-                match white_note {
-                    WhiteNote::C => write!(f, "B"),
-                    WhiteNote::F => write!(f, "E"),
-                    _ => write!(f, "{:?}b", white_note),
-                }
+            Note::Sharp(white_note) => write!(f, "{:?}#", white_note),
+            Note::Flat(white_note) => write!(f, "{:?}b", white_note),
+            Note::DoubleSharp(white_note) => write!(f, "{:?}##", white_note),
+            Note::DoubleFlat(white_note) => write!(f, "{:?}bb", white_note),
         }
     }
 }
@@ -124,7 +400,37 @@ impl std::hash::Hash for Note {
 
 impl PartialOrd for Note {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        self.get_index().partial_cmp(&other.get_index())
+        Some(self.cmp(other))
+    }
+}
+
+/// Notes are ordered by pitch class, from C (0) up to B (11). Enharmonic
+/// spellings such as C# and Db are equal, consistently with `PartialEq`.
+impl Ord for Note {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.get_index().cmp(&other.get_index())
+    }
+}
+
+impl From<WhiteNote> for Note {
+    fn from(white_note: WhiteNote) -> Note {
+        Note::WhiteNote(white_note)
+    }
+}
+
+impl std::ops::Add<Interval> for Note {
+    type Output = Note;
+
+    fn add(self, interval: Interval) -> Note {
+        self.up(interval)
+    }
+}
+
+impl std::ops::Sub<Interval> for Note {
+    type Output = Note;
+
+    fn sub(self, interval: Interval) -> Note {
+        self.down(interval)
     }
 }
 
@@ -143,6 +449,8 @@ impl Note {
                     WhiteNote::B => Note::Sharp(WhiteNote::C),
                 },
             Note::Flat(white_note) => Note::WhiteNote(white_note.clone()),
+            Note::DoubleSharp(_) => Note::from_index(self.get_index() + 1),
+            Note::DoubleFlat(white_note) => Note::Flat(white_note.clone()),
         }
     }
 
@@ -154,7 +462,7 @@ impl Note {
         note
     }
 
-    fn get_index(&self) -> u8 {
+    pub fn get_index(&self) -> u8 {
         match self {
             Note::WhiteNote(white_note) => match white_note {
                 WhiteNote::C => 0,
@@ -166,10 +474,9 @@ impl Note {
                 WhiteNote::B => 11,
             },
             Note::Sharp(white_note) => (1 + Note::WhiteNote(white_note.clone()).get_index()) % 12,
-            Note::Flat(white_note) => {
-                let white_note_index = Note::WhiteNote(white_note.clone()).get_index();
-                if white_note_index == 0 { 11 } else { white_note_index - 1 }
-            }
+            Note::Flat(white_note) => (11 + Note::WhiteNote(white_note.clone()).get_index()) % 12,
+            Note::DoubleSharp(white_note) => (2 + Note::WhiteNote(white_note.clone()).get_index()) % 12,
+            Note::DoubleFlat(white_note) => (10 + Note::WhiteNote(white_note.clone()).get_index()) % 12,
         }
     }
 
@@ -178,10 +485,14 @@ impl Note {
             Note::WhiteNote(white_note) => white_note.clone(),
             Note::Sharp(white_note) => white_note.clone(),
             Note::Flat(white_note) => white_note.clone(),
+            Note::DoubleSharp(white_note) => white_note.clone(),
+            Note::DoubleFlat(white_note) => white_note.clone(),
         }
     }
 
-    fn get_generic_interval(&self, other: &Note) -> u8 {
+    /// Number of letter names spanned from this note up to `other`, counting
+    /// both ends: 1 for a unison, 3 for a third, up to 7 for a seventh.
+    pub fn generic_interval(&self, other: &Note) -> u8 {
         let first = self.get_white_note();
         let second = other.get_white_note();
         let first_index = first.get_index();
@@ -189,40 +500,179 @@ impl Note {
         (second_index + 7 - first_index) % 7 + 1
     }
 
-    fn get_semitones(&self, other: &Note) -> u8 {
-        let mut note = self.clone();
-        let mut n_semitones = 0;
-        while note != *other {
-            note = note.up_semitone();
-            n_semitones += 1;
-        }
-        n_semitones
+    /// Number of semitones from this note up to the next occurrence of `other`,
+    /// between 0 and 11.
+    pub fn semitone_distance(&self, other: &Note) -> u8 {
+        (other.get_index() + 12 - self.get_index()) % 12
     }
 
-    fn add_accidentals(&self, other: WhiteNote) -> Note {
+    /// Spells this pitch class on the letter `other`, or `None` if that takes
+    /// more than two accidentals.
+    fn add_accidentals(&self, other: WhiteNote) -> Option<Note> {
         let other_note = Note::WhiteNote(other.clone());
-        if *self == other_note {
-           self.clone()
+        match (self.get_index() + 12 - other_note.get_index()) % 12 {
+            0 => Some(other_note),
+            1 => Some(Note::Sharp(other)),
+            2 => Some(Note::DoubleSharp(other)),
+            10 => Some(Note::DoubleFlat(other)),
+            11 => Some(Note::Flat(other)),
+            _ => None,
+        }
+    }
+
+    pub fn up_interval(&self, interval: Interval) -> Result<Note, IntervalError> {
+        let n_semitones = interval.get_number_semitones()?;
+        self.up_spelled(interval.number, n_semitones)
+            .ok_or(IntervalError::TooManyAccidentals(interval.quality, interval.number))
+    }
+
+    /// The note `semitones` above this one, spelled on the letter `number` steps
+    /// up (1 being the same letter), or `None` if that needs more than a double
+    /// accidental.
+    pub fn up_spelled(&self, number: u8, semitones: u8) -> Option<Note> {
+        let upper_white_note = self.get_white_note().nth_successor(number - 1);
+        self.up_semitones(semitones).add_accidentals(upper_white_note)
+    }
+
+    /// Same as `up_interval`, but panics if the interval is invalid. A note that
+    /// would need a triple accidental, such as the 7th of Cb°7, is given its
+    /// plain enharmonic spelling instead.
+    pub fn up(&self, interval: Interval) -> Note {
+        match self.up_interval(interval.clone()) {
+            Err(IntervalError::TooManyAccidentals(_, _)) =>
+                Note::from_index(self.get_index() + interval.get_number_semitones().unwrap() % 12),
+            result => result.expect("Invalid interval"),
         }
-        else if *self < other_note {
-            Note::Flat(other.clone())
+    }
+
+    pub fn down_interval(&self, interval: Interval) -> Result<Note, IntervalError> {
+        let n_semitones = interval.get_number_semitones()?;
+        let lower_white_note = self.get_white_note().nth_successor((7 - (interval.number - 1) % 7) % 7);
+        let lower_note = self.up_semitones((12 - n_semitones % 12) % 12);
+        lower_note.add_accidentals(lower_white_note)
+            .ok_or(IntervalError::TooManyAccidentals(interval.quality, interval.number))
+    }
+
+    /// Same as `down_interval`, but panics if the interval is invalid.
+    pub fn down(&self, interval: Interval) -> Note {
+        match self.down_interval(interval.clone()) {
+            Err(IntervalError::TooManyAccidentals(_, _)) =>
+                Note::from_index(self.get_index() + 12 - interval.get_number_semitones().unwrap() % 12),
+            result => result.expect("Invalid interval"),
         }
-        else {
-            Note::Sharp(other.clone())
+    }
+
+    pub fn chromatic_scale(use_sharps: bool) -> Vec<Note> {
+        let mut notes = Vec::new();
+        for white_note in WhiteNote::iter() {
+            notes.push(Note::WhiteNote(white_note.clone()));
+            match white_note {
+                WhiteNote::E | WhiteNote::B => (),
+                _ if use_sharps => notes.push(Note::Sharp(white_note)),
+                _ => notes.push(Note::Flat(white_note.successor())),
+            }
+        }
+        notes
+    }
+
+    /// Enharmonic spelling of this pitch class with a natural, a sharp or a flat.
+    pub fn respell(&self, use_sharps: bool) -> Note {
+        Note::chromatic_scale(use_sharps)[self.get_index() as usize].clone()
+    }
+
+    pub fn from_index(index: u8) -> Note {
+        match index % 12 {
+            0 => Note::WhiteNote(WhiteNote::C),
+            1 => Note::Flat(WhiteNote::D),
+            2 => Note::WhiteNote(WhiteNote::D),
+            3 => Note::Flat(WhiteNote::E),
+            4 => Note::WhiteNote(WhiteNote::E),
+            5 => Note::WhiteNote(WhiteNote::F),
+            6 => Note::Sharp(WhiteNote::F),
+            7 => Note::WhiteNote(WhiteNote::G),
+            8 => Note::Flat(WhiteNote::A),
+            9 => Note::WhiteNote(WhiteNote::A),
+            10 => Note::Flat(WhiteNote::B),
+            _ => Note::WhiteNote(WhiteNote::B),
+        }
+    }
+
+    pub fn transpose_semitones(&self, n: i8) -> Note {
+        Note::from_index(self.get_index() + n.rem_euclid(12) as u8)
+    }
+
+    fn get_midi_number(&self, octave: i8) -> i16 {
+        let white_note_index = Note::WhiteNote(self.get_white_note()).get_index() as i16;
+        let accidental = match self {
+            Note::WhiteNote(_) => 0,
+            Note::Sharp(_) => 1,
+            Note::Flat(_) => -1,
+            Note::DoubleSharp(_) => 2,
+            Note::DoubleFlat(_) => -2,
+        };
+        12 * (octave as i16 + 1) + white_note_index + accidental
+    }
+
+    pub fn to_midi(&self, octave: i8) -> Option<u8> {
+        u8::try_from(self.get_midi_number(octave)).ok().filter(|midi| *midi <= 127)
+    }
+
+    pub fn from_midi(midi: u8) -> Option<(Note, i8)> {
+        if midi > 127 {
+            return None;
         }
+        Some((Note::from_index(midi % 12), (midi / 12) as i8 - 1))
+    }
+
+    pub fn frequency(&self, octave: i8, a4: f64) -> f64 {
+        a4 * 2f64.powf((self.get_midi_number(octave) - 69) as f64 / 12.0)
+    }
+
+    /// Equal-tempered distance in cents from this pitch up to `other` in `other_octave`.
+    pub fn cents_to(&self, octave: i8, other: &Note, other_octave: i8) -> i32 {
+        100 * (other.get_midi_number(other_octave) - self.get_midi_number(octave)) as i32
     }
 
-    pub fn up_interval(&self, interval: Interval) -> Note {
-        let white_note = self.get_white_note();
-        let upper_white_note = white_note.nth_successor(interval.number - 1);
-        let n_semitones = interval.get_number_semitones();
-        let upper_note = self.up_semitones(n_semitones);
-        upper_note.add_accidentals(upper_white_note)
+    /// The pitch 12 semitones above this one in `octave`, keeping the spelling,
+    /// or `None` if the octave number would overflow.
+    pub fn octave_up(&self, octave: i8) -> Option<(Note, i8)> {
+        Some((self.clone(), octave.checked_add(1)?))
     }
 
+    /// The pitch 12 semitones below this one in `octave`, keeping the spelling,
+    /// or `None` if the octave number would overflow.
+    pub fn octave_down(&self, octave: i8) -> Option<(Note, i8)> {
+        Some((self.clone(), octave.checked_sub(1)?))
+    }
+
+    /// Other spellings of the same pitch class. Sharps and flats on E, B, C and
+    /// F, as well as double accidentals, are only included with `include_doubles`.
+    pub fn enharmonic_spellings(&self, include_doubles: bool) -> Vec<Note> {
+        WhiteNote::iter()
+            .flat_map(|white_note| [
+                Note::WhiteNote(white_note.clone()),
+                Note::Sharp(white_note.clone()),
+                Note::Flat(white_note.clone()),
+                Note::DoubleSharp(white_note.clone()),
+                Note::DoubleFlat(white_note),
+            ])
+            .filter(|note| note == self && note.to_string() != self.to_string())
+            .filter(|note| include_doubles || match note {
+                Note::WhiteNote(_) => true,
+                Note::Sharp(white_note) => !matches!(white_note, WhiteNote::E | WhiteNote::B),
+                Note::Flat(white_note) => !matches!(white_note, WhiteNote::C | WhiteNote::F),
+                Note::DoubleSharp(_) | Note::DoubleFlat(_) => false,
+            })
+            .collect()
+    }
+
+    /// Parses a letter, case-insensitive, followed by an optional "#", "b",
+    /// "##" or "bb". Surrounding whitespace is ignored; empty input, a lone
+    /// "#" (a lone "b" is the note B) or any other trailing character gives `None`.
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Note> {
-        let mut chars = s.chars();
-        let white_note = match chars.next() {
+        let mut chars = s.trim().chars();
+        let white_note = match chars.next().map(|c| c.to_ascii_uppercase()) {
             Some('C') => WhiteNote::C,
             Some('D') => WhiteNote::D,
             Some('E') => WhiteNote::E,
@@ -232,13 +682,82 @@ impl Note {
             Some('B') => WhiteNote::B,
             _ => return None,
         };
-        let accidental = match chars.next() {
-            Some('#') => Note::Sharp(white_note),
-            Some('b') => Note::Flat(white_note),
-            _ => Note::WhiteNote(white_note),
+        let accidental = match chars.as_str() {
+            "##" => Note::DoubleSharp(white_note),
+            "bb" => Note::DoubleFlat(white_note),
+            "#" => Note::Sharp(white_note),
+            "b" => Note::Flat(white_note),
+            "" => Note::WhiteNote(white_note),
+            _ => return None,
         };
         Some(accidental)
     }
+
+    /// Parses Helmholtz pitch notation, where "c'" is middle C (C4), "c" is C3,
+    /// "C" is C2 and "C," is C1. Accidentals are written "#" and "b".
+    pub fn from_helmholtz(s: &str) -> Option<(Note, i8)> {
+        let re = Regex::new(r"^([A-Ga-g])(##|bb|#|b)?('*|,*)$").unwrap();
+        let caps = re.captures(s.trim())?;
+        let letter = caps.get(1)?.as_str();
+        let accidental = caps.get(2).map_or("", |accidental| accidental.as_str());
+        let note = Note::from_str(&format!("{}{}", letter, accidental))?;
+        let markers = caps.get(3)?.as_str();
+        let n_markers = i16::try_from(markers.len()).ok()?;
+        let lowercase = letter.chars().all(|c| c.is_lowercase());
+        let octave = match (lowercase, markers.chars().next()) {
+            (true, Some('\'')) | (true, None) => n_markers.checked_add(3)?,
+            (false, Some(',')) | (false, None) => 2 - n_markers,
+            _ => return None,
+        };
+        Some((note, i8::try_from(octave).ok()?))
+    }
+
+    pub fn to_helmholtz(&self, octave: i8) -> String {
+        let name = self.to_string();
+        if octave >= 3 {
+            format!("{}{}", name[..1].to_lowercase(), &name[1..]) + &"'".repeat((octave - 3) as usize)
+        }
+        else {
+            name + &",".repeat((2 - octave as i16) as usize)
+        }
+    }
+}
+
+pub fn notes_in_midi_range(low: u8, high: u8) -> Result<Vec<(u8, Note)>, MidiError> {
+    if low > 127 {
+        return Err(MidiError::OutOfRange(low));
+    }
+    if high > 127 {
+        return Err(MidiError::OutOfRange(high));
+    }
+    if low > high {
+        return Err(MidiError::InvalidRange(low, high));
+    }
+    Ok((low..=high).map(|midi| (midi, Note::from_midi(midi).unwrap().0)).collect())
+}
+
+/// Closest equal-tempered note to `hz`, with its octave and the deviation in
+/// cents. Frequencies beyond the MIDI range are measured from notes 0 or 127.
+pub fn nearest_note(hz: f64, a4: f64) -> (Note, i8, f64) {
+    let midi = (69.0 + 12.0 * (hz / a4).log2()).round().clamp(0.0, 127.0) as u8;
+    let (note, octave) = Note::from_midi(midi).unwrap();
+    let cents = cents_between(note.frequency(octave, a4), hz);
+    (note, octave, cents)
+}
+
+pub fn cents_between(a: f64, b: f64) -> f64 {
+    1200.0 * (b / a).log2()
+}
+
+/// Sorts pitches from lowest to highest sounding, so B#3 and C4 compare equal.
+pub fn sort_pitches(pitches: &mut [(Note, i8)]) {
+    pitches.sort_by_key(|(note, octave)| note.get_midi_number(*octave));
+}
+
+/// Keeps only the first pitch of each pitch class, dropping octave doublings.
+pub fn dedup_pitch_class(pitches: &mut Vec<(Note, i8)>) {
+    let mut seen = HashSet::new();
+    pitches.retain(|(note, _)| seen.insert(note.clone()));
 }
 
 #[cfg(test)]
@@ -249,16 +768,16 @@ mod tests {
     fn test_note_display() {
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::C)), "C");
         assert_eq!(format!("{}", Note::Sharp(WhiteNote::C)), "C#");
-        assert_eq!(format!("{}", Note::Flat(WhiteNote::C)), "B");
+        assert_eq!(format!("{}", Note::Flat(WhiteNote::C)), "Cb");
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::D)), "D");
         assert_eq!(format!("{}", Note::Sharp(WhiteNote::D)), "D#");
         assert_eq!(format!("{}", Note::Flat(WhiteNote::D)), "Db");
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::E)), "E");
-        assert_eq!(format!("{}", Note::Sharp(WhiteNote::E)), "F");
+        assert_eq!(format!("{}", Note::Sharp(WhiteNote::E)), "E#");
         assert_eq!(format!("{}", Note::Flat(WhiteNote::E)), "Eb");
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::F)), "F");
         assert_eq!(format!("{}", Note::Sharp(WhiteNote::F)), "F#");
-        assert_eq!(format!("{}", Note::Flat(WhiteNote::F)), "E");
+        assert_eq!(format!("{}", Note::Flat(WhiteNote::F)), "Fb");
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::G)), "G");
         assert_eq!(format!("{}", Note::Sharp(WhiteNote::G)), "G#");
         assert_eq!(format!("{}", Note::Flat(WhiteNote::G)), "Gb");
@@ -266,7 +785,7 @@ mod tests {
         assert_eq!(format!("{}", Note::Sharp(WhiteNote::A)), "A#");
         assert_eq!(format!("{}", Note::Flat(WhiteNote::A)), "Ab");
         assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::B)), "B");
-        assert_eq!(format!("{}", Note::Sharp(WhiteNote::B)), "C");
+        assert_eq!(format!("{}", Note::Sharp(WhiteNote::B)), "B#");
         assert_eq!(format!("{}", Note::Flat(WhiteNote::B)), "Bb");
     }
 
@@ -288,42 +807,582 @@ mod tests {
 
     #[test]
     fn test_note_generic_interval() {
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::D)), 2);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::E)), 3);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::F)), 4);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::G)), 5);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::A)), 6);
-        assert_eq!(Note::WhiteNote(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::B)), 7);
-        assert_eq!(Note::Sharp(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
-        assert_eq!(Note::Sharp(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::D)), 2);
-        assert_eq!(Note::Flat(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
-        assert_eq!(Note::Flat(WhiteNote::C).get_generic_interval(&Note::WhiteNote(WhiteNote::B)), 7);
-        assert_eq!(Note::Sharp(WhiteNote::B).get_generic_interval(&Note::WhiteNote(WhiteNote::C)), 2);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::D)), 2);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::E)), 3);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::F)), 4);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::G)), 5);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::A)), 6);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::B)), 7);
+        assert_eq!(Note::Sharp(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
+        assert_eq!(Note::Sharp(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::D)), 2);
+        assert_eq!(Note::Flat(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::C)), 1);
+        assert_eq!(Note::Flat(WhiteNote::C).generic_interval(&Note::WhiteNote(WhiteNote::B)), 7);
+        assert_eq!(Note::Sharp(WhiteNote::B).generic_interval(&Note::WhiteNote(WhiteNote::C)), 2);
     }
 
     #[test]
     fn test_note_up_interval() {
-        assert_eq!(Note::WhiteNote(WhiteNote::C).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::E));
-        assert_eq!(Note::WhiteNote(WhiteNote::C).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::E));
-        assert_eq!(Note::WhiteNote(WhiteNote::C).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::G));
-        assert_eq!(Note::WhiteNote(WhiteNote::D).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::F));
-        assert_eq!(Note::WhiteNote(WhiteNote::D).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::F));
-        assert_eq!(Note::WhiteNote(WhiteNote::D).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::A));
-        assert_eq!(Note::WhiteNote(WhiteNote::E).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::G));
-        assert_eq!(Note::WhiteNote(WhiteNote::E).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::G));
-        assert_eq!(Note::WhiteNote(WhiteNote::E).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::B));
-        assert_eq!(Note::WhiteNote(WhiteNote::F).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::A));
-        assert_eq!(Note::WhiteNote(WhiteNote::F).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::A));
-        assert_eq!(Note::WhiteNote(WhiteNote::F).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::C));
-        assert_eq!(Note::WhiteNote(WhiteNote::G).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::B));
-        assert_eq!(Note::WhiteNote(WhiteNote::G).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::B));
-        assert_eq!(Note::WhiteNote(WhiteNote::G).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::D));
-        assert_eq!(Note::WhiteNote(WhiteNote::A).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::C));
-        assert_eq!(Note::WhiteNote(WhiteNote::A).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::C));
-        assert_eq!(Note::WhiteNote(WhiteNote::A).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::E));
-        assert_eq!(Note::WhiteNote(WhiteNote::B).up_interval(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::D));
-        assert_eq!(Note::WhiteNote(WhiteNote::B).up_interval(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::D));
-        assert_eq!(Note::WhiteNote(WhiteNote::B).up_interval(Interval::new(IntervalQuality::Perfect, 5)), Note::Sharp(WhiteNote::F));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::E));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::E));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::G));
+        assert_eq!(Note::WhiteNote(WhiteNote::D).up(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::F));
+        assert_eq!(Note::WhiteNote(WhiteNote::D).up(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::F));
+        assert_eq!(Note::WhiteNote(WhiteNote::D).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::A));
+        assert_eq!(Note::WhiteNote(WhiteNote::E).up(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::G));
+        assert_eq!(Note::WhiteNote(WhiteNote::E).up(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::G));
+        assert_eq!(Note::WhiteNote(WhiteNote::E).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::F).up(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::A));
+        assert_eq!(Note::WhiteNote(WhiteNote::F).up(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::A));
+        assert_eq!(Note::WhiteNote(WhiteNote::F).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(Note::WhiteNote(WhiteNote::G).up(Interval::new(IntervalQuality::Major, 3)), Note::WhiteNote(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::G).up(Interval::new(IntervalQuality::Minor, 3)), Note::Flat(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::G).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::D));
+        assert_eq!(Note::WhiteNote(WhiteNote::A).up(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::C));
+        assert_eq!(Note::WhiteNote(WhiteNote::A).up(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::C));
+        assert_eq!(Note::WhiteNote(WhiteNote::A).up(Interval::new(IntervalQuality::Perfect, 5)), Note::WhiteNote(WhiteNote::E));
+        assert_eq!(Note::WhiteNote(WhiteNote::B).up(Interval::new(IntervalQuality::Major, 3)), Note::Sharp(WhiteNote::D));
+        assert_eq!(Note::WhiteNote(WhiteNote::B).up(Interval::new(IntervalQuality::Minor, 3)), Note::WhiteNote(WhiteNote::D));
+        assert_eq!(Note::WhiteNote(WhiteNote::B).up(Interval::new(IntervalQuality::Perfect, 5)), Note::Sharp(WhiteNote::F));
+    }
+
+    #[test]
+    fn test_note_ordering() {
+        assert!(Note::WhiteNote(WhiteNote::C) < Note::Sharp(WhiteNote::C));
+        assert!(Note::Flat(WhiteNote::D) < Note::WhiteNote(WhiteNote::D));
+        assert!(Note::WhiteNote(WhiteNote::B) > Note::Sharp(WhiteNote::A));
+        assert_eq!(Note::Sharp(WhiteNote::C).cmp(&Note::Flat(WhiteNote::D)), std::cmp::Ordering::Equal);
+
+        let mut notes = vec![
+            Note::WhiteNote(WhiteNote::G),
+            Note::Flat(WhiteNote::E),
+            Note::WhiteNote(WhiteNote::B),
+            Note::WhiteNote(WhiteNote::C),
+            Note::Sharp(WhiteNote::F),
+        ];
+        notes.sort();
+        assert_eq!(notes, vec![
+            Note::WhiteNote(WhiteNote::C),
+            Note::Flat(WhiteNote::E),
+            Note::Sharp(WhiteNote::F),
+            Note::WhiteNote(WhiteNote::G),
+            Note::WhiteNote(WhiteNote::B),
+        ]);
+    }
+
+    #[test]
+    fn test_interval_invert() {
+        assert_eq!(Interval::new(IntervalQuality::Major, 3).invert(), Interval::new(IntervalQuality::Minor, 6));
+        assert_eq!(Interval::new(IntervalQuality::Minor, 7).invert(), Interval::new(IntervalQuality::Major, 2));
+        assert_eq!(Interval::new(IntervalQuality::Perfect, 5).invert(), Interval::new(IntervalQuality::Perfect, 4));
+        assert_eq!(Interval::new(IntervalQuality::Augmented, 4).invert(), Interval::new(IntervalQuality::Diminished, 5));
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 5).invert(), Interval::new(IntervalQuality::Augmented, 4));
+        assert_eq!(Interval::new(IntervalQuality::Perfect, 1).invert(), Interval::new(IntervalQuality::Perfect, 8));
+        assert_eq!(Interval::new(IntervalQuality::Perfect, 8).invert(), Interval::new(IntervalQuality::Perfect, 1));
+    }
+
+    #[test]
+    fn test_note_to_midi() {
+        assert_eq!(Note::WhiteNote(WhiteNote::C).to_midi(4), Some(60));
+        assert_eq!(Note::WhiteNote(WhiteNote::A).to_midi(4), Some(69));
+        assert_eq!(Note::Sharp(WhiteNote::F).to_midi(4), Some(66));
+        assert_eq!(Note::Flat(WhiteNote::B).to_midi(3), Some(58));
+        assert_eq!(Note::Flat(WhiteNote::C).to_midi(4), Some(59));
+        assert_eq!(Note::Sharp(WhiteNote::B).to_midi(3), Some(60));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).to_midi(-1), Some(0));
+        assert_eq!(Note::WhiteNote(WhiteNote::G).to_midi(9), Some(127));
+        assert_eq!(Note::Flat(WhiteNote::C).to_midi(-1), None);
+        assert_eq!(Note::Sharp(WhiteNote::G).to_midi(9), None);
+    }
+
+    #[test]
+    fn test_interval_try_new() {
+        assert_eq!(Interval::try_new(IntervalQuality::Major, 3), Some(Interval::new(IntervalQuality::Major, 3)));
+        assert_eq!(Interval::try_new(IntervalQuality::Perfect, 5), Some(Interval::new(IntervalQuality::Perfect, 5)));
+        assert_eq!(Interval::try_new(IntervalQuality::Augmented, 4), Some(Interval::new(IntervalQuality::Augmented, 4)));
+        assert_eq!(Interval::try_new(IntervalQuality::Perfect, 3), None);
+        assert_eq!(Interval::try_new(IntervalQuality::Major, 5), None);
+        assert_eq!(Interval::try_new(IntervalQuality::Minor, 9), Some(Interval::new(IntervalQuality::Minor, 9)));
+        assert_eq!(Interval::try_new(IntervalQuality::Major, 254), None);
+        assert_eq!(Interval::try_new(IntervalQuality::Perfect, 155), None);
+        assert!(Interval::try_new(IntervalQuality::Perfect, 148).is_some());
+        assert_eq!(Interval::new(IntervalQuality::Major, 254).get_number_semitones(), Err(IntervalError::InvalidInterval(IntervalQuality::Major, 254)));
+        assert_eq!(Interval::try_new(IntervalQuality::Perfect, 11), Some(Interval::new(IntervalQuality::Perfect, 11)));
+        assert_eq!(Interval::try_new(IntervalQuality::Major, 11), None);
+        assert_eq!(Interval::try_new(IntervalQuality::Diminished, 1), None);
+    }
+
+    #[test]
+    fn test_note_up_invalid_interval() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(c.up_interval(Interval::new(IntervalQuality::Major, 3)), Ok(Note::WhiteNote(WhiteNote::E)));
+        assert_eq!(c.up_interval(Interval::new(IntervalQuality::Perfect, 3)), Err(IntervalError::InvalidInterval(IntervalQuality::Perfect, 3)));
+        assert_eq!(c.up_interval(Interval::new(IntervalQuality::Minor, 5)), Err(IntervalError::InvalidInterval(IntervalQuality::Minor, 5)));
+        assert_eq!(c.up_interval(Interval::new(IntervalQuality::Perfect, 10)), Err(IntervalError::InvalidInterval(IntervalQuality::Perfect, 10)));
+        assert_eq!(c.up_interval(Interval::new(IntervalQuality::Diminished, 1)), Err(IntervalError::InvalidInterval(IntervalQuality::Diminished, 1)));
+    }
+
+    #[test]
+    fn test_note_get_index() {
+        assert_eq!(Note::WhiteNote(WhiteNote::C).get_index(), 0);
+        assert_eq!(Note::Sharp(WhiteNote::C).get_index(), 1);
+        assert_eq!(Note::Flat(WhiteNote::C).get_index(), 11);
+        assert_eq!(Note::Flat(WhiteNote::F).get_index(), 4);
+        assert_eq!(Note::Sharp(WhiteNote::E).get_index(), 5);
+        assert_eq!(Note::Sharp(WhiteNote::B).get_index(), 0);
+        assert_eq!(Note::Flat(WhiteNote::G).get_index(), 6);
+    }
+
+    #[test]
+    fn test_note_semitone_distance() {
+        let notes: Vec<Note> = WhiteNote::iter()
+            .flat_map(|white_note| [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::Flat(white_note)])
+            .collect();
+        for first in notes.iter() {
+            for second in notes.iter() {
+                let mut note = first.clone();
+                let mut n_semitones = 0;
+                while note != *second {
+                    note = note.up_semitone();
+                    n_semitones += 1;
+                }
+                assert_eq!(first.semitone_distance(second), n_semitones);
+            }
+        }
+        assert_eq!(Note::WhiteNote(WhiteNote::C).semitone_distance(&Note::WhiteNote(WhiteNote::G)), 7);
+        assert_eq!(Note::WhiteNote(WhiteNote::G).semitone_distance(&Note::WhiteNote(WhiteNote::C)), 5);
+        assert_eq!(Note::Sharp(WhiteNote::C).semitone_distance(&Note::Flat(WhiteNote::D)), 0);
+    }
+
+    #[test]
+    fn test_note_transpose_semitones() {
+        assert_eq!(Note::WhiteNote(WhiteNote::C).transpose_semitones(7), Note::WhiteNote(WhiteNote::G));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).transpose_semitones(-1), Note::WhiteNote(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::A).transpose_semitones(1), Note::Flat(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::F).transpose_semitones(13), Note::Sharp(WhiteNote::F));
+        assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::D).transpose_semitones(-3)), "B");
+        assert_eq!(format!("{}", Note::WhiteNote(WhiteNote::G).transpose_semitones(1)), "Ab");
+        assert_eq!(Note::WhiteNote(WhiteNote::B).transpose_semitones(120), Note::WhiteNote(WhiteNote::B));
+        assert_eq!(Note::WhiteNote(WhiteNote::B).transpose_semitones(127), Note::Sharp(WhiteNote::F));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).transpose_semitones(-128), Note::WhiteNote(WhiteNote::E));
+    }
+
+    #[test]
+    fn test_interval_diminished() {
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 5).get_number_semitones(), Ok(6));
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 7).get_number_semitones(), Ok(9));
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 3).get_number_semitones(), Ok(2));
+        assert_eq!(Note::WhiteNote(WhiteNote::B).up(Interval::new(IntervalQuality::Diminished, 7)), Note::Flat(WhiteNote::A));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Diminished, 7)).to_string(), "Bbb");
+    }
+
+    #[test]
+    fn test_note_frequency() {
+        assert_eq!(Note::WhiteNote(WhiteNote::A).frequency(4, 440.0), 440.0);
+        assert_eq!(Note::WhiteNote(WhiteNote::A).frequency(5, 440.0), 880.0);
+        assert_eq!(Note::WhiteNote(WhiteNote::A).frequency(4, 432.0), 432.0);
+        assert_eq!(format!("{:.2}", Note::WhiteNote(WhiteNote::C).frequency(4, 440.0)), "261.63");
+        assert_eq!(format!("{:.2}", Note::Sharp(WhiteNote::F).frequency(2, 440.0)), "92.50");
+        assert_eq!(format!("{:.2}", Note::WhiteNote(WhiteNote::C).frequency(-2, 440.0)), "4.09");
+    }
+
+    #[test]
+    fn test_note_from_midi() {
+        assert_eq!(Note::from_midi(60), Some((Note::WhiteNote(WhiteNote::C), 4)));
+        assert_eq!(Note::from_midi(69), Some((Note::WhiteNote(WhiteNote::A), 4)));
+        assert_eq!(Note::from_midi(70), Some((Note::Flat(WhiteNote::B), 4)));
+        assert_eq!(Note::from_midi(0), Some((Note::WhiteNote(WhiteNote::C), -1)));
+        assert_eq!(Note::from_midi(127), Some((Note::WhiteNote(WhiteNote::G), 9)));
+        assert_eq!(Note::from_midi(128), None);
+    }
+
+    #[test]
+    fn test_notes_in_midi_range() {
+        let notes = notes_in_midi_range(60, 72).unwrap();
+        assert_eq!(notes.len(), 13);
+        assert_eq!(notes[0], (60, Note::WhiteNote(WhiteNote::C)));
+        assert_eq!(notes[6], (66, Note::Sharp(WhiteNote::F)));
+        assert_eq!(notes[12], (72, Note::WhiteNote(WhiteNote::C)));
+        assert_eq!(notes_in_midi_range(64, 64).unwrap(), vec![(64, Note::WhiteNote(WhiteNote::E))]);
+        assert_eq!(notes_in_midi_range(72, 60), Err(MidiError::InvalidRange(72, 60)));
+        assert_eq!(notes_in_midi_range(60, 128), Err(MidiError::OutOfRange(128)));
+        assert_eq!(notes_in_midi_range(200, 210), Err(MidiError::OutOfRange(200)));
+    }
+
+    #[test]
+    fn test_interval_from_str() {
+        assert_eq!(Interval::from_str("P5"), Some(Interval::new(IntervalQuality::Perfect, 5)));
+        assert_eq!(Interval::from_str("M3"), Some(Interval::new(IntervalQuality::Major, 3)));
+        assert_eq!(Interval::from_str("m7"), Some(Interval::new(IntervalQuality::Minor, 7)));
+        assert_eq!(Interval::from_str("A4"), Some(Interval::new(IntervalQuality::Augmented, 4)));
+        assert_eq!(Interval::from_str("d5"), Some(Interval::new(IntervalQuality::Diminished, 5)));
+        assert_eq!(Interval::from_str("perfect 5"), Some(Interval::new(IntervalQuality::Perfect, 5)));
+        assert_eq!(Interval::from_str("minor 3"), Some(Interval::new(IntervalQuality::Minor, 3)));
+        assert_eq!(Interval::from_str("P3"), None);
+        assert_eq!(Interval::from_str("M5"), None);
+        assert_eq!(Interval::from_str("X3"), None);
+        assert_eq!(Interval::from_str("M"), None);
+        assert_eq!(Interval::from_str(""), None);
+    }
+
+    #[test]
+    fn test_interval_augmented() {
+        assert_eq!(Interval::new(IntervalQuality::Augmented, 1).get_number_semitones(), Ok(1));
+        assert_eq!(Interval::new(IntervalQuality::Augmented, 2).get_number_semitones(), Ok(3));
+        assert_eq!(Interval::new(IntervalQuality::Augmented, 4).get_number_semitones(), Ok(6));
+        assert_eq!(Interval::new(IntervalQuality::Augmented, 6).get_number_semitones(), Ok(10));
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 7).get_number_semitones(), Ok(9));
+        assert_eq!(Interval::new(IntervalQuality::Diminished, 4).get_number_semitones(), Ok(4));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Augmented, 2)), Note::Sharp(WhiteNote::D));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up(Interval::new(IntervalQuality::Augmented, 6)).to_string(), "A#");
+        assert_eq!(Interval::try_new(IntervalQuality::Augmented, 2), Some(Interval::new(IntervalQuality::Augmented, 2)));
+    }
+
+    #[test]
+    fn test_note_distances() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let e_flat = Note::Flat(WhiteNote::E);
+        let d_sharp = Note::Sharp(WhiteNote::D);
+        assert_eq!(c.generic_interval(&e_flat), 3);
+        assert_eq!(c.semitone_distance(&e_flat), 3);
+        assert_eq!(c.generic_interval(&d_sharp), 2);
+        assert_eq!(c.semitone_distance(&d_sharp), 3);
+        assert_eq!(e_flat.generic_interval(&c), 6);
+        assert_eq!(e_flat.semitone_distance(&c), 9);
+        assert_eq!(Note::WhiteNote(WhiteNote::B).generic_interval(&Note::Sharp(WhiteNote::F)), 5);
+        assert_eq!(Note::WhiteNote(WhiteNote::B).semitone_distance(&Note::Sharp(WhiteNote::F)), 7);
+    }
+
+    #[test]
+    fn test_interval_between() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(Interval::between(&c, &Note::WhiteNote(WhiteNote::E)), Some(Interval::new(IntervalQuality::Major, 3)));
+        assert_eq!(Interval::between(&c, &Note::Flat(WhiteNote::E)), Some(Interval::new(IntervalQuality::Minor, 3)));
+        assert_eq!(Interval::between(&c, &Note::WhiteNote(WhiteNote::G)), Some(Interval::new(IntervalQuality::Perfect, 5)));
+        assert_eq!(Interval::between(&c, &Note::Sharp(WhiteNote::F)), Some(Interval::new(IntervalQuality::Augmented, 4)));
+        assert_eq!(Interval::between(&c, &Note::Flat(WhiteNote::G)), Some(Interval::new(IntervalQuality::Diminished, 5)));
+        assert_eq!(Interval::between(&c, &c), Some(Interval::new(IntervalQuality::Perfect, 1)));
+        assert_eq!(Interval::between(&Note::WhiteNote(WhiteNote::B), &Note::WhiteNote(WhiteNote::F)), Some(Interval::new(IntervalQuality::Diminished, 5)));
+        assert_eq!(Interval::between(&Note::Flat(WhiteNote::C), &Note::WhiteNote(WhiteNote::B)), Some(Interval::new(IntervalQuality::Augmented, 7)));
+        assert_eq!(Interval::between(&Note::Sharp(WhiteNote::B), &Note::WhiteNote(WhiteNote::C)), Some(Interval::new(IntervalQuality::Diminished, 2)));
+    }
+
+    #[test]
+    fn test_interval_between_pitches() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let e = Note::WhiteNote(WhiteNote::E);
+        assert_eq!(Interval::between_pitches((&c, 4), (&e, 5)), Some(Interval::new(IntervalQuality::Major, 10)));
+        assert_eq!(Interval::between_pitches((&c, 4), (&c, 5)), Some(Interval::new(IntervalQuality::Perfect, 8)));
+        assert_eq!(Interval::between_pitches((&c, 4), (&e, 4)), Some(Interval::new(IntervalQuality::Major, 3)));
+        assert_eq!(Interval::between_pitches((&c, 4), (&Note::WhiteNote(WhiteNote::D), 5)), Some(Interval::new(IntervalQuality::Major, 9)));
+        assert_eq!(Interval::between_pitches((&c, 3), (&Note::WhiteNote(WhiteNote::G), 5)), Some(Interval::new(IntervalQuality::Perfect, 19)));
+        assert_eq!(Interval::between_pitches((&c, 4), (&c, 6)), Some(Interval::new(IntervalQuality::Perfect, 15)));
+        assert_eq!(Interval::between_pitches((&e, 4), (&c, 4)), None);
+    }
+
+    #[test]
+    fn test_note_double_accidentals() {
+        assert_eq!(format!("{}", Note::DoubleSharp(WhiteNote::F)), "F##");
+        assert_eq!(format!("{}", Note::DoubleFlat(WhiteNote::B)), "Bbb");
+        assert_eq!(Note::DoubleSharp(WhiteNote::F), Note::WhiteNote(WhiteNote::G));
+        assert_eq!(Note::DoubleFlat(WhiteNote::C), Note::Flat(WhiteNote::B));
+        assert_eq!(Note::from_str("F##"), Some(Note::DoubleSharp(WhiteNote::F)));
+        assert_eq!(Note::from_str("Dbb").map(|n| n.to_string()), Some("Dbb".to_string()));
+        assert_eq!(Note::DoubleSharp(WhiteNote::B).to_midi(3), Some(61));
+        assert_eq!(Note::Sharp(WhiteNote::E).up(Interval::new(IntervalQuality::Major, 2)).to_string(), "F##");
+    }
+
+    #[test]
+    fn test_note_enharmonic_spellings() {
+        assert_eq!(Note::Sharp(WhiteNote::F).enharmonic_spellings(false), vec![Note::Flat(WhiteNote::G)]);
+        assert!(Note::WhiteNote(WhiteNote::C).enharmonic_spellings(false).is_empty());
+
+        let spellings: Vec<String> = Note::WhiteNote(WhiteNote::C).enharmonic_spellings(true).iter().map(|n| n.to_string()).collect();
+        assert_eq!(spellings, vec!["Dbb", "B#"]);
+
+        let spellings: Vec<String> = Note::Sharp(WhiteNote::F).enharmonic_spellings(true).iter().map(|n| n.to_string()).collect();
+        assert_eq!(spellings, vec!["E##", "Gb"]);
+
+        let spellings: Vec<String> = Note::WhiteNote(WhiteNote::E).enharmonic_spellings(false).iter().map(|n| n.to_string()).collect();
+        assert!(spellings.is_empty());
+        let spellings: Vec<String> = Note::WhiteNote(WhiteNote::E).enharmonic_spellings(true).iter().map(|n| n.to_string()).collect();
+        assert_eq!(spellings, vec!["D##", "Fb"]);
+    }
+
+    #[test]
+    fn test_chromatic_scale() {
+        let sharps = Note::chromatic_scale(true);
+        let names: Vec<String> = sharps.iter().map(|n| n.to_string()).collect();
+        assert_eq!(names.join(" "), "C C# D D# E F F# G G# A A# B");
+        let indices: Vec<u8> = sharps.iter().map(|n| n.get_index()).collect();
+        assert_eq!(indices, (0..12).collect::<Vec<u8>>());
+
+        let flats = Note::chromatic_scale(false);
+        let names: Vec<String> = flats.iter().map(|n| n.to_string()).collect();
+        assert_eq!(names.join(" "), "C Db D Eb E F Gb G Ab A Bb B");
+        let indices: Vec<u8> = flats.iter().map(|n| n.get_index()).collect();
+        assert_eq!(indices, (0..12).collect::<Vec<u8>>());
+    }
+
+    #[test]
+    fn test_down_interval() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(c.down(Interval::new(IntervalQuality::Major, 3)).to_string(), "Ab");
+        assert_eq!(c.down(Interval::new(IntervalQuality::Perfect, 5)).to_string(), "F");
+        assert_eq!(c.down(Interval::new(IntervalQuality::Minor, 2)).to_string(), "B");
+        assert_eq!(c.down(Interval::new(IntervalQuality::Augmented, 4)).to_string(), "Gb");
+        assert_eq!(c.down(Interval::new(IntervalQuality::Perfect, 8)).to_string(), "C");
+        assert_eq!(c.down(Interval::new(IntervalQuality::Perfect, 1)).to_string(), "C");
+        assert_eq!(Note::Sharp(WhiteNote::F).down(Interval::new(IntervalQuality::Major, 6)).to_string(), "A");
+        assert!(c.down_interval(Interval::new(IntervalQuality::Perfect, 3)).is_err());
+    }
+
+    #[test]
+    fn test_interval_operators() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!((c.clone() + Interval::new(IntervalQuality::Major, 3)).to_string(), "E");
+        assert_eq!((c.clone() + Interval::new(IntervalQuality::Minor, 7)).to_string(), "Bb");
+        assert_eq!((c.clone() - Interval::new(IntervalQuality::Major, 2)).to_string(), "Bb");
+        assert_eq!((Note::WhiteNote(WhiteNote::E) - Interval::new(IntervalQuality::Major, 3)).to_string(), "C");
+        let interval = Interval::new(IntervalQuality::Perfect, 4);
+        assert_eq!(c.clone() + interval.clone() - interval, c);
+    }
+
+    #[test]
+    fn test_interval_constructors() {
+        assert_eq!(Interval::perfect_unison().get_number_semitones(), Ok(0));
+        assert_eq!(Interval::augmented_unison().get_number_semitones(), Ok(1));
+        assert_eq!(Interval::minor_second().get_number_semitones(), Ok(1));
+        assert_eq!(Interval::major_second().get_number_semitones(), Ok(2));
+        assert_eq!(Interval::augmented_second().get_number_semitones(), Ok(3));
+        assert_eq!(Interval::minor_third().get_number_semitones(), Ok(3));
+        assert_eq!(Interval::major_third().get_number_semitones(), Ok(4));
+        assert_eq!(Interval::perfect_fourth().get_number_semitones(), Ok(5));
+        assert_eq!(Interval::augmented_fourth().get_number_semitones(), Ok(6));
+        assert_eq!(Interval::diminished_fifth().get_number_semitones(), Ok(6));
+        assert_eq!(Interval::perfect_fifth().get_number_semitones(), Ok(7));
+        assert_eq!(Interval::augmented_fifth().get_number_semitones(), Ok(8));
+        assert_eq!(Interval::minor_sixth().get_number_semitones(), Ok(8));
+        assert_eq!(Interval::major_sixth().get_number_semitones(), Ok(9));
+        assert_eq!(Interval::diminished_seventh().get_number_semitones(), Ok(9));
+        assert_eq!(Interval::minor_seventh().get_number_semitones(), Ok(10));
+        assert_eq!(Interval::major_seventh().get_number_semitones(), Ok(11));
+        assert_eq!(Interval::octave().get_number_semitones(), Ok(12));
+    }
+
+    #[test]
+    fn test_respell() {
+        assert_eq!(Note::Sharp(WhiteNote::C).respell(false).to_string(), "Db");
+        assert_eq!(Note::Flat(WhiteNote::D).respell(true).to_string(), "C#");
+        assert_eq!(Note::Sharp(WhiteNote::E).respell(true).to_string(), "F");
+        assert_eq!(Note::DoubleFlat(WhiteNote::B).respell(false).to_string(), "A");
+        assert_eq!(Note::WhiteNote(WhiteNote::G).respell(false).to_string(), "G");
+    }
+
+    #[test]
+    fn test_from_white_note() {
+        assert_eq!(Note::from(WhiteNote::C), Note::WhiteNote(WhiteNote::C));
+        let note: Note = WhiteNote::F.into();
+        assert_eq!(note, Note::WhiteNote(WhiteNote::F));
+        assert_eq!(Note::from(WhiteNote::B) + Interval::minor_second(), Note::WhiteNote(WhiteNote::C));
+    }
+
+    #[test]
+    fn test_up_compound_interval() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(c.up(Interval::new(IntervalQuality::Major, 9)).to_string(), "D");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Minor, 9)).to_string(), "Db");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Augmented, 9)).to_string(), "D#");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Perfect, 11)).to_string(), "F");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Augmented, 11)).to_string(), "F#");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Major, 13)).to_string(), "A");
+        assert_eq!(c.up(Interval::new(IntervalQuality::Perfect, 15)).to_string(), "C");
+        assert_eq!(Interval::new(IntervalQuality::Major, 9).get_number_semitones(), Ok(14));
+        assert_eq!(Interval::new(IntervalQuality::Perfect, 11).get_number_semitones(), Ok(17));
+        assert_eq!(Interval::new(IntervalQuality::Major, 13).get_number_semitones(), Ok(21));
+        assert_eq!(Note::WhiteNote(WhiteNote::E).down(Interval::new(IntervalQuality::Major, 10)).to_string(), "C");
+        assert_eq!(Interval::from_str("M9"), Some(Interval::new(IntervalQuality::Major, 9)));
+        assert_eq!(Interval::new(IntervalQuality::Major, 9).invert(), Interval::new(IntervalQuality::Minor, 7));
+    }
+
+    #[test]
+    fn test_nearest_note() {
+        let (note, octave, cents) = nearest_note(440.0, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::A), 4));
+        assert!(cents.abs() < 1e-9);
+
+        let (note, octave, cents) = nearest_note(445.0, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::A), 4));
+        assert!(cents > 19.0 && cents < 20.0);
+
+        let (note, octave, cents) = nearest_note(82.0, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::E), 2));
+        assert!(cents < 0.0);
+
+        let (note, octave, _) = nearest_note(261.63, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::C), 4));
+
+        let (note, octave, cents) = nearest_note(1.0, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::C), -1));
+        assert!(cents < -1200.0);
+
+        let (note, octave, _) = nearest_note(100000.0, 440.0);
+        assert_eq!((note, octave), (Note::WhiteNote(WhiteNote::G), 9));
+    }
+
+    #[test]
+    fn test_cents() {
+        assert!((cents_between(440.0, 880.0) - 1200.0).abs() < 1e-9);
+        assert!((cents_between(880.0, 440.0) + 1200.0).abs() < 1e-9);
+        let c = Note::WhiteNote(WhiteNote::C);
+        let g = Note::WhiteNote(WhiteNote::G);
+        assert!((cents_between(c.frequency(4, 440.0), g.frequency(4, 440.0)) - 700.0).abs() < 1e-9);
+        assert!((cents_between(2.0, 3.0) - 701.955).abs() < 1e-3);
+
+        assert_eq!(c.cents_to(4, &g, 4), 700);
+        assert_eq!(c.cents_to(4, &c, 5), 1200);
+        assert_eq!(g.cents_to(4, &c, 4), -700);
+        assert_eq!(Note::Sharp(WhiteNote::B).cents_to(3, &c, 4), 0);
+    }
+
+    #[test]
+    fn test_interval_accessors() {
+        let interval = Interval::new(IntervalQuality::Minor, 7);
+        assert_eq!(interval.quality(), &IntervalQuality::Minor);
+        assert_eq!(interval.number(), 7);
+        let interval = Interval::between(&Note::WhiteNote(WhiteNote::D), &Note::WhiteNote(WhiteNote::A)).unwrap();
+        assert_eq!(interval.quality(), &IntervalQuality::Perfect);
+        assert_eq!(interval.number(), 5);
+    }
+
+    #[test]
+    fn test_helmholtz() {
+        assert_eq!(Note::from_helmholtz("c'"), Some((Note::WhiteNote(WhiteNote::C), 4)));
+        assert_eq!(Note::from_helmholtz("c"), Some((Note::WhiteNote(WhiteNote::C), 3)));
+        assert_eq!(Note::from_helmholtz("C"), Some((Note::WhiteNote(WhiteNote::C), 2)));
+        assert_eq!(Note::from_helmholtz("C,"), Some((Note::WhiteNote(WhiteNote::C), 1)));
+        assert_eq!(Note::from_helmholtz("a'"), Some((Note::WhiteNote(WhiteNote::A), 4)));
+        assert_eq!(Note::from_helmholtz("f#''"), Some((Note::Sharp(WhiteNote::F), 5)));
+        assert_eq!(Note::from_helmholtz("bb"), Some((Note::Flat(WhiteNote::B), 3)));
+        assert_eq!(Note::from_helmholtz("Eb,,"), Some((Note::Flat(WhiteNote::E), 0)));
+        assert_eq!(Note::from_helmholtz("c,"), None);
+        assert_eq!(Note::from_helmholtz("C'"), None);
+        assert_eq!(Note::from_helmholtz("h'"), None);
+        assert_eq!(Note::from_helmholtz(&format!("c{}", "'".repeat(124))), Some((Note::WhiteNote(WhiteNote::C), 127)));
+        assert_eq!(Note::from_helmholtz(&format!("c{}", "'".repeat(125))), None);
+        assert_eq!(Note::from_helmholtz(&format!("C{}", ",".repeat(130))), Some((Note::WhiteNote(WhiteNote::C), -128)));
+        assert_eq!(Note::from_helmholtz(&format!("C{}", ",".repeat(131))), None);
+        assert_eq!(Note::from_helmholtz(&format!("C{}", ",".repeat(300))), None);
+        assert_eq!(Note::from_helmholtz(&format!("c{}", "'".repeat(32767))), None);
+        assert_eq!(Note::WhiteNote(WhiteNote::C).to_helmholtz(-128), format!("C{}", ",".repeat(130)));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).to_helmholtz(127), format!("c{}", "'".repeat(124)));
+
+        for token in ["c'", "c", "C", "C,", "f#''", "bb", "Eb,,", "g''''"] {
+            let (note, octave) = Note::from_helmholtz(token).unwrap();
+            assert_eq!(note.to_helmholtz(octave), token);
+        }
+        assert_eq!(Note::from_helmholtz("a'").map(|(note, octave)| note.to_midi(octave)), Some(Some(69)));
+    }
+
+    #[test]
+    fn test_octave_up_down() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(c.octave_up(4), Some((c.clone(), 5)));
+        assert_eq!(c.octave_down(4), Some((c.clone(), 3)));
+        assert_eq!(c.octave_up(i8::MAX), None);
+        assert_eq!(c.octave_down(i8::MIN), None);
+        let (note, octave) = Note::Sharp(WhiteNote::B).octave_up(3).unwrap();
+        assert_eq!(note.to_string(), "B#");
+        assert_eq!(note.to_midi(octave).unwrap(), Note::Sharp(WhiteNote::B).to_midi(3).unwrap() + 12);
+    }
+
+    #[test]
+    fn test_interval_add() {
+        assert_eq!(Interval::major_third().add(&Interval::minor_third()), Interval::perfect_fifth());
+        assert_eq!(Interval::major_third().add(&Interval::major_third()), Interval::augmented_fifth());
+        assert_eq!(Interval::minor_third().add(&Interval::minor_third()), Interval::diminished_fifth());
+        assert_eq!(Interval::perfect_fifth().add(&Interval::perfect_fourth()), Interval::octave());
+        assert_eq!(Interval::perfect_fifth().add(&Interval::major_sixth()), Interval::new(IntervalQuality::Major, 10));
+        assert_eq!(Interval::augmented_fifth().try_add(&Interval::augmented_fourth()), None);
+        let zero = Interval::new(IntervalQuality::Perfect, 0);
+        assert_eq!(zero.try_add(&zero), None);
+        assert_eq!(Interval::major_third().try_add(&zero), None);
+        assert_eq!(Interval::from_number_and_semitones(0, 0), None);
+    }
+
+    #[test]
+    fn test_sort_and_dedup_pitches() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let e = Note::WhiteNote(WhiteNote::E);
+        let g = Note::WhiteNote(WhiteNote::G);
+        let mut pitches = vec![(e.clone(), 4), (c.clone(), 5), (g.clone(), 3), (c.clone(), 4)];
+        sort_pitches(&mut pitches);
+        assert_eq!(pitches, vec![(g.clone(), 3), (c.clone(), 4), (e.clone(), 4), (c.clone(), 5)]);
+        dedup_pitch_class(&mut pitches);
+        assert_eq!(pitches, vec![(g, 3), (c, 4), (e, 4)]);
+
+        let mut pitches = vec![(Note::WhiteNote(WhiteNote::D), 4), (Note::Sharp(WhiteNote::B), 3)];
+        sort_pitches(&mut pitches);
+        assert_eq!(pitches[0].1, 3);
+    }
+
+    #[test]
+    fn test_note_from_str_edge_cases() {
+        assert_eq!(Note::from_str(""), None);
+        assert_eq!(Note::from_str("   "), None);
+        assert_eq!(Note::from_str("#"), None);
+        assert_eq!(Note::from_str("b"), Some(Note::WhiteNote(WhiteNote::B)));
+        assert_eq!(Note::from_str("bb").map(|n| n.to_string()), Some("Bb".to_string()));
+        assert_eq!(Note::from_str(" C# ").map(|n| n.to_string()), Some("C#".to_string()));
+        assert_eq!(Note::from_str("C #"), None);
+        assert_eq!(Note::from_str("Cx"), None);
+        assert_eq!(Note::from_str("C###"), None);
+        assert_eq!(Note::from_str("H"), None);
+    }
+
+    #[test]
+    fn test_too_many_accidentals() {
+        let b_double_sharp = Note::DoubleSharp(WhiteNote::B);
+        assert_eq!(b_double_sharp.up_interval(Interval::augmented_fifth()), Err(IntervalError::TooManyAccidentals(IntervalQuality::Augmented, 5)));
+        assert_eq!(b_double_sharp.up_spelled(5, 8), None);
+        assert_eq!(Note::DoubleFlat(WhiteNote::F).down_interval(Interval::augmented_fourth()), Err(IntervalError::TooManyAccidentals(IntervalQuality::Augmented, 4)));
+        assert_eq!(Note::WhiteNote(WhiteNote::C).up_spelled(3, 3).map(|note| note.to_string()), Some("Eb".to_string()));
+        assert_eq!(Note::Flat(WhiteNote::C).up(Interval::diminished_seventh()).to_string(), "Ab");
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        for white_note in WhiteNote::iter() {
+            assert_eq!(WhiteNote::from_str(&white_note.to_string()), Some(white_note.clone()));
+            for note in [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::DoubleFlat(white_note)] {
+                assert_eq!(Note::from_str(&note.to_string()).map(|n| n.to_string()), Some(note.to_string()));
+            }
+        }
+        assert_eq!(WhiteNote::from_str("C#"), None);
+        for quality in [IntervalQuality::Perfect, IntervalQuality::Major, IntervalQuality::Minor, IntervalQuality::Augmented, IntervalQuality::Diminished] {
+            for number in 1..=15 {
+                if let Some(interval) = Interval::try_new(quality.clone(), number) {
+                    assert_eq!(Interval::from_str(&interval.to_string()), Some(interval));
+                }
+            }
+        }
+        assert_eq!(Interval::major_third().to_string(), "M3");
+        assert_eq!(Interval::diminished_fifth().to_string(), "d5");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let note = Note::DoubleFlat(WhiteNote::B);
+        let json = serde_json::to_string(&note).unwrap();
+        assert_eq!(json, "\"Bbb\"");
+        assert_eq!(serde_json::from_str::<Note>(&json).unwrap().to_string(), "Bbb");
+        assert!(serde_json::from_str::<Note>("\"H\"").is_err());
+
+        let interval = Interval::minor_seventh();
+        let json = serde_json::to_string(&interval).unwrap();
+        assert_eq!(serde_json::from_str::<Interval>(&json).unwrap(), interval);
+        let json = serde_json::to_string(&WhiteNote::F).unwrap();
+        assert_eq!(serde_json::from_str::<WhiteNote>(&json).unwrap(), WhiteNote::F);
     }
 }
@@ -0,0 +1,88 @@
+use crate::notes::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PitchClassSet {
+    pitch_classes: Vec<u8>,
+}
+
+impl PitchClassSet {
+    pub fn new(pitch_classes: &[u8]) -> PitchClassSet {
+        let mut pitch_classes: Vec<u8> = pitch_classes.iter().map(|pc| pc % 12).collect();
+        pitch_classes.sort();
+        pitch_classes.dedup();
+        PitchClassSet { pitch_classes }
+    }
+
+    pub fn from_notes(notes: &[Note]) -> PitchClassSet {
+        PitchClassSet::new(&notes.iter().map(|note| note.get_index()).collect::<Vec<u8>>())
+    }
+
+    pub fn pitch_classes(&self) -> &[u8] {
+        &self.pitch_classes
+    }
+
+    fn packing(pitch_classes: &[u8]) -> Vec<u8> {
+        pitch_classes.iter().rev().map(|pc| (pc + 12 - pitch_classes[0]) % 12).collect()
+    }
+
+    fn most_packed(candidates: Vec<Vec<u8>>) -> Vec<u8> {
+        candidates.into_iter()
+            .min_by_key(|candidate| (PitchClassSet::packing(candidate), candidate.first().copied()))
+            .unwrap_or_default()
+    }
+
+    pub fn normal_form(&self) -> Vec<u8> {
+        let n = self.pitch_classes.len();
+        let rotations = (0..n)
+            .map(|i| (0..n).map(|j| self.pitch_classes[(i + j) % n]).collect())
+            .collect();
+        PitchClassSet::most_packed(rotations)
+    }
+
+    pub fn prime_form(&self) -> Vec<u8> {
+        let inversion = PitchClassSet::new(&self.pitch_classes.iter().map(|pc| (12 - pc) % 12).collect::<Vec<u8>>());
+        let zeroed = |normal_form: Vec<u8>| normal_form.iter().map(|pc| (pc + 12 - normal_form[0]) % 12).collect();
+        PitchClassSet::most_packed(vec![zeroed(self.normal_form()), zeroed(inversion.normal_form())])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pitch_class_set_from_notes() {
+        let set = PitchClassSet::from_notes(&[
+            Note::WhiteNote(WhiteNote::G),
+            Note::WhiteNote(WhiteNote::C),
+            Note::WhiteNote(WhiteNote::E),
+            Note::Sharp(WhiteNote::B),
+        ]);
+        assert_eq!(set.pitch_classes(), &[0, 4, 7]);
+        assert_eq!(PitchClassSet::new(&[14, 2, 11]).pitch_classes(), &[2, 11]);
+    }
+
+    #[test]
+    fn test_pitch_class_set_normal_form() {
+        assert_eq!(PitchClassSet::new(&[0, 4, 7]).normal_form(), vec![0, 4, 7]);
+        assert_eq!(PitchClassSet::new(&[2, 6, 9]).normal_form(), vec![2, 6, 9]);
+        assert_eq!(PitchClassSet::new(&[0, 4, 9]).normal_form(), vec![9, 0, 4]);
+        assert_eq!(PitchClassSet::new(&[0, 11, 1]).normal_form(), vec![11, 0, 1]);
+        assert_eq!(PitchClassSet::new(&[0, 3, 6, 9]).normal_form(), vec![0, 3, 6, 9]);
+        assert_eq!(PitchClassSet::new(&[]).normal_form(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_pitch_class_set_prime_form() {
+        let c_major = PitchClassSet::from_notes(&[Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G)]);
+        let d_major = PitchClassSet::from_notes(&[Note::WhiteNote(WhiteNote::D), Note::Sharp(WhiteNote::F), Note::WhiteNote(WhiteNote::A)]);
+        let c_minor = PitchClassSet::from_notes(&[Note::WhiteNote(WhiteNote::C), Note::Flat(WhiteNote::E), Note::WhiteNote(WhiteNote::G)]);
+        assert_eq!(c_major.prime_form(), vec![0, 3, 7]);
+        assert_eq!(d_major.prime_form(), c_major.prime_form());
+        assert_eq!(c_minor.prime_form(), c_major.prime_form());
+        assert_eq!(PitchClassSet::new(&[1, 2, 3]).prime_form(), vec![0, 1, 2]);
+        assert_eq!(PitchClassSet::new(&[0, 4, 8]).prime_form(), vec![0, 4, 8]);
+        assert_eq!(PitchClassSet::new(&[0, 1, 4, 6]).prime_form(), vec![0, 1, 4, 6]);
+        assert_eq!(PitchClassSet::new(&[0, 2, 5, 6]).prime_form(), vec![0, 1, 4, 6]);
+    }
+}
@@ -0,0 +1,146 @@
+use std::cmp::Reverse;
+use strum::IntoEnumIterator;
+use crate::notes::*;
+use crate::chords::*;
+use crate::scales::*;
+
+pub fn parse_roman_numeral(s: &str, key: &Scale) -> Option<Chord> {
+    let (numeral, diminished) = match s.strip_suffix('°').or_else(|| s.strip_suffix('o')) {
+        Some(numeral) => (numeral, true),
+        None => (s, false),
+    };
+    let degree = match numeral.to_uppercase().as_str() {
+        "I" => 0,
+        "II" => 1,
+        "III" => 2,
+        "IV" => 3,
+        "V" => 4,
+        "VI" => 5,
+        "VII" => 6,
+        _ => return None,
+    };
+    let quality = if diminished {
+        ChordQuality::Diminished
+    }
+    else if numeral.chars().all(|c| c.is_uppercase()) {
+        ChordQuality::Major
+    }
+    else if numeral.chars().all(|c| c.is_lowercase()) {
+        ChordQuality::Minor
+    }
+    else {
+        return None;
+    };
+    let root = key.get_notes().get(degree)?.clone();
+    Some(Chord::new(root, quality))
+}
+
+pub fn parse_progression(s: &str, key: &Scale) -> Option<Vec<Chord>> {
+    s.split_whitespace()
+        .map(|token| parse_roman_numeral(token, key).or_else(|| Chord::from_str(token)))
+        .collect()
+}
+
+pub fn transpose_progression(chords: &[Chord], semitones: i8) -> Vec<Chord> {
+    chords.iter().map(|chord| chord.transpose_semitones(semitones)).collect()
+}
+
+pub fn detect_key(chords: &[Chord]) -> Vec<Scale> {
+    let mut keys: Vec<(Scale, usize)> = Vec::new();
+    for white_note in WhiteNote::iter() {
+        for root in [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::Flat(white_note)].iter() {
+            for scale_type in [ScaleType::Major, ScaleType::Minor] {
+                let key = Scale::new(root.clone(), scale_type);
+                if keys.iter().any(|(other, _)| *other == key) {
+                    continue;
+                }
+                let diatonic_chords = key.diatonic_chords().unwrap_or_default();
+                let n_diatonic = chords.iter().filter(|chord| diatonic_chords.contains(chord)).count();
+                if n_diatonic == 0 {
+                    continue;
+                }
+                let n_tonic = match key.tonic() {
+                    Some(tonic) => [chords.first(), chords.last()].iter().filter(|chord| **chord == Some(&tonic)).count(),
+                    None => 0,
+                };
+                keys.push((key, 2 * n_diatonic + n_tonic));
+            }
+        }
+    }
+    keys.sort_by_key(|(_, score)| Reverse(*score));
+    keys.into_iter().map(|(key, _)| key).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_roman_numeral() {
+        let key = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(parse_roman_numeral("I", &key), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major)));
+        assert_eq!(parse_roman_numeral("ii", &key), Some(Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor)));
+        assert_eq!(parse_roman_numeral("iv", &key), Some(Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Minor)));
+        assert_eq!(parse_roman_numeral("vii°", &key), Some(Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished)));
+        assert_eq!(parse_roman_numeral("viio", &key), Some(Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished)));
+        assert_eq!(parse_roman_numeral("Iv", &key), None);
+        assert_eq!(parse_roman_numeral("VIII", &key), None);
+        let whole_tone = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone);
+        assert_eq!(parse_roman_numeral("VII", &whole_tone), None);
+    }
+
+    #[test]
+    fn test_parse_progression() {
+        let key = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(parse_progression("I V vi IV", &key), Some(vec![
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major),
+        ]));
+
+        let key = Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Major);
+        assert_eq!(parse_progression("I V vi IV", &key), Some(vec![
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::E), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+        ]));
+
+        assert_eq!(parse_progression("C G Am F", &key), Some(vec![
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major),
+        ]));
+
+        assert_eq!(parse_progression("I X", &key), None);
+    }
+
+    #[test]
+    fn test_detect_key() {
+        let chords = parse_progression("C G Am F", &Scale::from_str("C major").unwrap()).unwrap();
+        let keys = detect_key(&chords);
+        assert_eq!(keys[0], Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major));
+        assert_eq!(keys[1], Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor));
+
+        let chords = parse_progression("Am Dm E Am", &Scale::from_str("C major").unwrap()).unwrap();
+        assert_eq!(detect_key(&chords)[0], Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor));
+
+        let chords = parse_progression("D A Bm G", &Scale::from_str("C major").unwrap()).unwrap();
+        assert_eq!(detect_key(&chords)[0], Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Major));
+
+        assert!(detect_key(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_transpose_progression() {
+        let chords = parse_progression("C Am F G", &Scale::from_str("C major").unwrap()).unwrap();
+        let transposed = transpose_progression(&chords, 2);
+        assert_eq!(transposed, parse_progression("D Bm G A", &Scale::from_str("C major").unwrap()).unwrap());
+        let symbols: Vec<String> = transposed.iter().map(|chord| chord.to_string()).collect();
+        assert_eq!(symbols, vec!["D", "Bm", "G", "A"]);
+        assert_eq!(transpose_progression(&transposed, -2), chords);
+        assert!(transpose_progression(&[], 5).is_empty());
+    }
+}
@@ -0,0 +1,86 @@
+use crate::notes::*;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Duration {
+    Whole,
+    Half,
+    Quarter,
+    Eighth,
+    Sixteenth,
+    ThirtySecond,
+    Dotted(Box<Duration>),
+}
+
+impl Duration {
+    pub fn dotted(self) -> Duration {
+        Duration::Dotted(Box::new(self))
+    }
+
+    /// Length in quarter note beats.
+    pub fn beats(&self) -> f64 {
+        match self {
+            Duration::Whole => 4.0,
+            Duration::Half => 2.0,
+            Duration::Quarter => 1.0,
+            Duration::Eighth => 0.5,
+            Duration::Sixteenth => 0.25,
+            Duration::ThirtySecond => 0.125,
+            Duration::Dotted(duration) => 1.5 * duration.beats(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedNote {
+    note: Note,
+    duration: Duration,
+}
+
+impl TimedNote {
+    pub fn new(note: Note, duration: Duration) -> TimedNote {
+        TimedNote { note, duration }
+    }
+
+    pub fn note(&self) -> &Note {
+        &self.note
+    }
+
+    pub fn duration(&self) -> &Duration {
+        &self.duration
+    }
+
+    pub fn beats(&self) -> f64 {
+        self.duration.beats()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_beats() {
+        assert_eq!(Duration::Whole.beats(), 4.0);
+        assert_eq!(Duration::Half.beats(), 2.0);
+        assert_eq!(Duration::Quarter.beats(), 1.0);
+        assert_eq!(Duration::Eighth.beats(), 0.5);
+        assert_eq!(Duration::Sixteenth.beats(), 0.25);
+        assert_eq!(Duration::ThirtySecond.beats(), 0.125);
+    }
+
+    #[test]
+    fn test_dotted_duration() {
+        assert_eq!(Duration::Half.dotted().beats(), 3.0);
+        assert_eq!(Duration::Quarter.dotted(), Duration::Dotted(Box::new(Duration::Quarter)));
+        assert_eq!(Duration::Quarter.dotted().beats(), 1.5);
+        assert_eq!(Duration::Quarter.dotted().dotted().beats(), 2.25);
+    }
+
+    #[test]
+    fn test_timed_note() {
+        let note = TimedNote::new(Note::WhiteNote(WhiteNote::A), Duration::Eighth.dotted());
+        assert_eq!(note.note(), &Note::WhiteNote(WhiteNote::A));
+        assert_eq!(note.duration(), &Duration::Dotted(Box::new(Duration::Eighth)));
+        assert_eq!(note.beats(), 0.75);
+    }
+}
@@ -1,66 +1,628 @@
+use std::collections::HashSet;
 use std::fmt;
 use regex::Regex;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
 use crate::notes::*;
+use crate::chords::*;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ScaleType {
     Major,
     Minor,
+    WholeTone,
+    Chromatic,
+    Dorian,
+    Phrygian,
+    Lydian,
+    Mixolydian,
+    Locrian,
+    Altered,
+    LydianDominant,
+    DiminishedWholeHalf,
+    DiminishedHalfWhole,
+    #[strum(disabled)]
+    Custom(Vec<u8>),
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Half,
+    Whole,
+    Augmented,
+    Other(u8),
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Half => write!(f, "H"),
+            Step::Whole => write!(f, "W"),
+            Step::Augmented => write!(f, "A"),
+            Step::Other(semitones) => write!(f, "{}", semitones),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Scale {
     root: Note,
     scale_type: ScaleType,
 }
 
-impl fmt::Display for Scale {
+impl ScaleType {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ScaleType::Major => "major",
+            ScaleType::Minor => "minor",
+            ScaleType::WholeTone => "whole tone",
+            ScaleType::Chromatic => "chromatic",
+            ScaleType::Dorian => "dorian",
+            ScaleType::Phrygian => "phrygian",
+            ScaleType::Lydian => "lydian",
+            ScaleType::Mixolydian => "mixolydian",
+            ScaleType::Locrian => "locrian",
+            ScaleType::Altered => "altered",
+            ScaleType::LydianDominant => "lydian dominant",
+            ScaleType::DiminishedWholeHalf => "whole-half diminished",
+            ScaleType::DiminishedHalfWhole => "half-whole diminished",
+            ScaleType::Custom(_) => "custom",
+        }
+    }
+
+    /// Names accepted by `Scale::from_str` besides the `symbol` of each type.
+    fn aliases() -> Vec<(ScaleType, &'static str)> {
+        vec![
+            (ScaleType::Major, "ionian"),
+            (ScaleType::Minor, "aeolian"),
+            (ScaleType::Altered, "super locrian"),
+        ]
+    }
+
+    /// Names accepted by `Scale::from_str` for each scale type, aliases included.
+    pub fn keywords() -> Vec<(ScaleType, &'static str)> {
+        ScaleType::iter()
+            .map(|scale_type| {
+                let symbol = scale_type.symbol();
+                (scale_type, symbol)
+            })
+            .chain(ScaleType::aliases())
+            .collect()
+    }
+
+    /// Scale type named `keyword`, accepting the same names as `Scale::from_str`.
+    pub fn from_symbol(keyword: &str) -> Option<ScaleType> {
+        let keyword = keyword.to_lowercase();
+        ScaleType::keywords().into_iter()
+            .find(|(_, other)| *other == keyword)
+            .map(|(scale_type, _)| scale_type)
+    }
+
+    fn is_mode(&self) -> bool {
+        matches!(self, ScaleType::Dorian | ScaleType::Phrygian | ScaleType::Lydian | ScaleType::Mixolydian | ScaleType::Locrian)
+    }
+
+    /// Total number of semitones by which the degrees sit above those of the
+    /// major scale, from 1 for Lydian down to -5 for Locrian. Scales without
+    /// seven notes have a brightness of 0.
+    pub fn brightness(&self) -> i8 {
+        let root = Note::WhiteNote(WhiteNote::C);
+        let notes = Scale::new(root.clone(), self.clone()).get_notes();
+        if notes.len() != 7 {
+            return 0;
+        }
+        notes.iter().map(|note| root.semitone_distance(note) as i8).sum::<i8>() - 38
+    }
+}
+
+impl fmt::Display for ScaleType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.root, match self.scale_type {
-            ScaleType::Major => "major scale",
-            ScaleType::Minor => "minor scale",
+        write!(f, "{}", match self {
+            ScaleType::Major => "Major",
+            ScaleType::Minor => "Minor",
+            ScaleType::WholeTone => "Whole Tone",
+            ScaleType::Chromatic => "Chromatic",
+            ScaleType::Dorian => "Dorian",
+            ScaleType::Phrygian => "Phrygian",
+            ScaleType::Lydian => "Lydian",
+            ScaleType::Mixolydian => "Mixolydian",
+            ScaleType::Locrian => "Locrian",
+            ScaleType::Altered => "Altered",
+            ScaleType::LydianDominant => "Lydian Dominant",
+            ScaleType::DiminishedWholeHalf => "Whole-Half Diminished",
+            ScaleType::DiminishedHalfWhole => "Half-Whole Diminished",
+            ScaleType::Custom(_) => "Custom",
         })
     }
 }
 
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} scale", self.root, self.scale_type.symbol())
+    }
+}
+
 impl Scale {
     pub fn new(root: Note, scale_type: ScaleType) -> Scale {
         Scale { root, scale_type, }
     }
 
+    pub fn root(&self) -> &Note {
+        &self.root
+    }
+
+    pub fn scale_type(&self) -> &ScaleType {
+        &self.scale_type
+    }
+
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Scale> {
-        let re = Regex::new(r"([A-Ga-g][#b]?)\s*((?:major|minor))").unwrap();
+        let mut keywords = ScaleType::keywords();
+        keywords.sort_by_key(|(_, keyword)| std::cmp::Reverse(keyword.len()));
+        let alternatives: Vec<String> = keywords.iter().map(|(_, keyword)| regex::escape(keyword)).collect();
+        let re = Regex::new(&format!(r"([A-Ga-g][#b]?)\s*((?i:{}))", alternatives.join("|"))).unwrap();
         let caps = re.captures(s)?;
         let root = Note::from_str(caps.get(1)?.as_str())?;
-        let scale_type = match caps.get(2)?.as_str() {
-            "major" => ScaleType::Major,
-            "minor" => ScaleType::Minor,
-            _ => return None,
-        };
+        let name = caps.get(2)?.as_str().to_lowercase();
+        let (scale_type, _) = keywords.into_iter().find(|(_, keyword)| *keyword == name)?;
         Some(Scale::new(root, scale_type))
     }
 
+    /// Builds a scale from the semitone steps between consecutive notes.
+    /// Seven-note scales get one letter per degree. Steps must be positive and
+    /// fit within an octave, otherwise notes would repeat.
+    pub fn from_intervals(root: Note, intervals: &[u8]) -> Option<Scale> {
+        let total: u32 = intervals.iter().map(|step| *step as u32).sum();
+        if intervals.contains(&0) || total > 12 {
+            return None;
+        }
+        Some(Scale::new(root, ScaleType::Custom(intervals.to_vec())))
+    }
+
     pub fn get_notes(&self) -> Vec<Note> {
-        match self.scale_type {
+        match &self.scale_type {
             ScaleType::Major => vec![
                 self.root.clone(),
-                self.root.up_interval(Interval::new(IntervalQuality::Major, 2)),
-                self.root.up_interval(Interval::new(IntervalQuality::Major, 3)),
-                self.root.up_interval(Interval::new(IntervalQuality::Perfect, 4)),
-                self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5)),
-                self.root.up_interval(Interval::new(IntervalQuality::Major, 6)),
-                self.root.up_interval(Interval::new(IntervalQuality::Major, 7)),
+                self.root.up(Interval::major_second()),
+                self.root.up(Interval::major_third()),
+                self.root.up(Interval::perfect_fourth()),
+                self.root.up(Interval::perfect_fifth()),
+                self.root.up(Interval::major_sixth()),
+                self.root.up(Interval::major_seventh()),
             ],
             ScaleType::Minor => vec![
                 self.root.clone(),
-                self.root.up_interval(Interval::new(IntervalQuality::Major, 2)),
-                self.root.up_interval(Interval::new(IntervalQuality::Minor, 3)),
-                self.root.up_interval(Interval::new(IntervalQuality::Perfect, 4)),
-                self.root.up_interval(Interval::new(IntervalQuality::Perfect, 5)),
-                self.root.up_interval(Interval::new(IntervalQuality::Minor, 6)),
-                self.root.up_interval(Interval::new(IntervalQuality::Minor, 7)),
+                self.root.up(Interval::major_second()),
+                self.root.up(Interval::minor_third()),
+                self.root.up(Interval::perfect_fourth()),
+                self.root.up(Interval::perfect_fifth()),
+                self.root.up(Interval::minor_sixth()),
+                self.root.up(Interval::minor_seventh()),
+            ],
+            ScaleType::Dorian => self.stack_on_root(&[
+                Interval::major_second(),
+                Interval::minor_third(),
+                Interval::perfect_fourth(),
+                Interval::perfect_fifth(),
+                Interval::major_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::Phrygian => self.stack_on_root(&[
+                Interval::minor_second(),
+                Interval::minor_third(),
+                Interval::perfect_fourth(),
+                Interval::perfect_fifth(),
+                Interval::minor_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::Lydian => self.stack_on_root(&[
+                Interval::major_second(),
+                Interval::major_third(),
+                Interval::augmented_fourth(),
+                Interval::perfect_fifth(),
+                Interval::major_sixth(),
+                Interval::major_seventh(),
+            ]),
+            ScaleType::Mixolydian => self.stack_on_root(&[
+                Interval::major_second(),
+                Interval::major_third(),
+                Interval::perfect_fourth(),
+                Interval::perfect_fifth(),
+                Interval::major_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::Locrian => self.stack_on_root(&[
+                Interval::minor_second(),
+                Interval::minor_third(),
+                Interval::perfect_fourth(),
+                Interval::diminished_fifth(),
+                Interval::minor_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::Altered => self.stack_on_root(&[
+                Interval::minor_second(),
+                Interval::minor_third(),
+                Interval::new(IntervalQuality::Diminished, 4),
+                Interval::diminished_fifth(),
+                Interval::minor_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::LydianDominant => self.stack_on_root(&[
+                Interval::major_second(),
+                Interval::major_third(),
+                Interval::augmented_fourth(),
+                Interval::perfect_fifth(),
+                Interval::major_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::DiminishedWholeHalf => self.stack_on_root(&[
+                Interval::major_second(),
+                Interval::minor_third(),
+                Interval::perfect_fourth(),
+                Interval::diminished_fifth(),
+                Interval::minor_sixth(),
+                Interval::major_sixth(),
+                Interval::major_seventh(),
+            ]),
+            ScaleType::DiminishedHalfWhole => self.stack_on_root(&[
+                Interval::minor_second(),
+                Interval::minor_third(),
+                Interval::major_third(),
+                Interval::augmented_fourth(),
+                Interval::perfect_fifth(),
+                Interval::major_sixth(),
+                Interval::minor_seventh(),
+            ]),
+            ScaleType::WholeTone => {
+                let mut notes = vec![self.root.clone()];
+                for _ in 1..6 {
+                    let note = notes.last().unwrap().up(Interval::major_second());
+                    notes.push(note);
+                }
+                notes
+            },
+            ScaleType::Chromatic => {
+                let mut notes = vec![self.root.clone()];
+                for _ in 1..12 {
+                    let note = notes.last().unwrap();
+                    let interval = match note {
+                        Note::WhiteNote(WhiteNote::E) | Note::WhiteNote(WhiteNote::B) | Note::Sharp(_) | Note::DoubleSharp(_) =>
+                            Interval::minor_second(),
+                        Note::WhiteNote(_) | Note::Flat(_) | Note::DoubleFlat(_) => Interval::augmented_unison(),
+                    };
+                    let note = note.up(interval);
+                    notes.push(note);
+                }
+                notes
+            },
+            ScaleType::Custom(_) => {
+                let offsets = self.semitone_offsets();
+                offsets.iter().enumerate().map(|(degree, offset)| {
+                    if offsets.len() == 7 {
+                        self.root.up_spelled(degree as u8 + 1, *offset)
+                            .unwrap_or_else(|| self.root.transpose_semitones(*offset as i8))
+                    }
+                    else {
+                        self.root.transpose_semitones(*offset as i8)
+                    }
+                }).collect()
+            },
+        }
+    }
+
+    /// Semitones from the root to each note of the scale.
+    pub fn semitone_offsets(&self) -> Vec<u8> {
+        match &self.scale_type {
+            ScaleType::Major => vec![0, 2, 4, 5, 7, 9, 11],
+            ScaleType::Minor => vec![0, 2, 3, 5, 7, 8, 10],
+            ScaleType::Dorian => vec![0, 2, 3, 5, 7, 9, 10],
+            ScaleType::Phrygian => vec![0, 1, 3, 5, 7, 8, 10],
+            ScaleType::Lydian => vec![0, 2, 4, 6, 7, 9, 11],
+            ScaleType::Mixolydian => vec![0, 2, 4, 5, 7, 9, 10],
+            ScaleType::Locrian => vec![0, 1, 3, 5, 6, 8, 10],
+            ScaleType::Altered => vec![0, 1, 3, 4, 6, 8, 10],
+            ScaleType::LydianDominant => vec![0, 2, 4, 6, 7, 9, 10],
+            ScaleType::DiminishedWholeHalf => vec![0, 2, 3, 5, 6, 8, 9, 11],
+            ScaleType::DiminishedHalfWhole => vec![0, 1, 3, 4, 6, 7, 9, 10],
+            ScaleType::WholeTone => (0..12).step_by(2).collect(),
+            ScaleType::Chromatic => (0..12).collect(),
+            ScaleType::Custom(steps) => {
+                let mut offsets: Vec<u8> = vec![0];
+                for step in steps {
+                    offsets.push(offsets.last().unwrap().saturating_add(*step));
+                }
+                if offsets.last() == Some(&12) {
+                    offsets.pop();
+                }
+                offsets.iter().map(|offset| offset % 12).collect()
+            },
+        }
+    }
+
+    fn stack_on_root(&self, intervals: &[Interval]) -> Vec<Note> {
+        let mut notes = vec![self.root.clone()];
+        notes.extend(intervals.iter().map(|interval| self.root.up(interval.clone())));
+        notes
+    }
+
+    /// Interval from the root to each degree, starting with the perfect unison,
+    /// or `None` if a degree is spelled too far from the root to name one.
+    pub fn intervals(&self) -> Option<Vec<Interval>> {
+        self.get_notes().iter().map(|note| Interval::between(&self.root, note)).collect()
+    }
+
+    /// Steps between consecutive notes, ending with the step back to the tonic.
+    pub fn step_pattern(&self) -> Vec<Step> {
+        let notes = self.get_notes_with_octave_tonic();
+        notes.windows(2).map(|pair| match pair[0].semitone_distance(&pair[1]) {
+            1 => Step::Half,
+            2 => Step::Whole,
+            3 => Step::Augmented,
+            semitones => Step::Other(semitones),
+        }).collect()
+    }
+
+    /// Notes of `self` missing from `other`, and notes of `other` missing from `self`.
+    pub fn diff(&self, other: &Scale) -> (Vec<Note>, Vec<Note>) {
+        let notes = self.get_notes();
+        let other_notes = other.get_notes();
+        (
+            notes.iter().filter(|note| !other_notes.contains(note)).cloned().collect(),
+            other_notes.iter().filter(|note| !notes.contains(note)).cloned().collect(),
+        )
+    }
+
+    pub fn contains(&self, note: &Note) -> bool {
+        self.get_notes().contains(note)
+    }
+
+    pub fn contains_chord(&self, chord: &Chord) -> bool {
+        chord.get_notes().iter().all(|note| self.contains(note))
+    }
+
+    pub fn get_notes_with_octave_tonic(&self) -> Vec<Note> {
+        let mut notes = self.get_notes();
+        notes.push(self.root.clone());
+        notes
+    }
+
+    pub fn transpose(&self, interval: Interval) -> Scale {
+        Scale::new(self.root.up(interval), self.scale_type.clone())
+    }
+
+    pub fn notes_over_octaves(&self, octaves: u8) -> Vec<Note> {
+        let mut notes: Vec<Note> = (0..octaves).flat_map(|_| self.get_notes()).collect();
+        notes.push(self.root.clone());
+        notes
+    }
+
+    /// Notes of `octaves` octaves of the scale with their octave numbers, the
+    /// root starting in `octave`, or `None` if an octave number does not fit in an `i8`.
+    pub fn pitches_over_octaves(&self, octaves: u8, octave: i8) -> Option<Vec<(Note, i8)>> {
+        let notes = self.get_notes();
+        let root_midi = self.root.to_midi(4);
+        let mut pitches = Vec::new();
+        for n in 0..octaves {
+            for note in notes.iter() {
+                let wraps = note.to_midi(4) < root_midi;
+                let pitch_octave = octave as i16 + n as i16 + wraps as i16;
+                pitches.push((note.clone(), i8::try_from(pitch_octave).ok()?));
+            }
+        }
+        pitches.push((self.root.clone(), i8::try_from(octave as i16 + octaves as i16).ok()?));
+        Some(pitches)
+    }
+
+    /// Practice pattern pairing each degree with the degree `step` notes above
+    /// it, counting both ends: 3 gives C E, D F, E G... in C major. A step
+    /// of 0 names no interval and gives an empty pattern.
+    pub fn in_intervals(&self, step: u8) -> Vec<Note> {
+        if step == 0 {
+            return Vec::new();
+        }
+        let notes = self.get_notes();
+        (0..notes.len())
+            .flat_map(|degree| [notes[degree].clone(), notes[(degree + step as usize - 1) % notes.len()].clone()])
+            .collect()
+    }
+
+    pub fn in_thirds(&self) -> Vec<Note> {
+        self.in_intervals(3)
+    }
+
+    /// Chord of `size` notes stacked in thirds on `degree`, if its quality has a name.
+    fn stacked_chord(notes: &[Note], degree: usize, size: usize) -> Option<Chord> {
+        let root = notes[degree].clone();
+        let stacked: Vec<Note> = (0..size).map(|third| notes[(degree + 2 * third) % notes.len()].clone()).collect();
+        ChordQuality::iter()
+            .map(|quality| Chord::new(root.clone(), quality))
+            .find(|chord| chord.get_notes() == stacked)
+    }
+
+    /// Quality of the triad on each degree, or `None` if one of them has no name,
+    /// as in the whole tone scale.
+    pub fn degree_qualities(&self) -> Option<Vec<ChordQuality>> {
+        self.diatonic_chords().map(|chords| chords.into_iter().map(|chord| chord.quality().clone()).collect())
+    }
+
+    pub fn diatonic_chords(&self) -> Option<Vec<Chord>> {
+        let notes = self.get_notes();
+        (0..notes.len()).map(|degree| Scale::stacked_chord(&notes, degree, 3)).collect()
+    }
+
+    /// Seventh chords stacked in thirds on each degree, or `None` if one of
+    /// them has no name.
+    pub fn diatonic_sevenths(&self) -> Option<Vec<Chord>> {
+        let notes = self.get_notes();
+        (0..notes.len()).map(|degree| Scale::stacked_chord(&notes, degree, 4)).collect()
+    }
+
+    /// Triad then seventh chord of each degree, labelled with roman numerals.
+    pub fn full_harmony(&self) -> Option<Vec<(String, Chord)>> {
+        let harmony = self.diatonic_chords()?.into_iter()
+            .zip(self.diatonic_sevenths()?)
+            .enumerate()
+            .flat_map(|(degree, (triad, seventh))| [
+                (triad.roman_numeral(degree as u8 + 1).unwrap_or_else(|| (degree + 1).to_string()), triad),
+                (seventh.roman_numeral(degree as u8 + 1).unwrap_or_else(|| (degree + 1).to_string()), seventh),
+            ])
+            .collect();
+        Some(harmony)
+    }
+
+    pub fn tonic(&self) -> Option<Chord> {
+        self.diatonic_chords()?.first().cloned()
+    }
+
+    pub fn subdominant(&self) -> Option<Chord> {
+        self.diatonic_chords()?.get(3).cloned()
+    }
+
+    pub fn dominant(&self) -> Option<Chord> {
+        self.diatonic_chords()?.get(4).cloned()
+    }
+
+    pub fn modes(&self) -> Vec<Scale> {
+        let notes = self.get_notes();
+        let diatonic_modes = [
+            ScaleType::Major,
+            ScaleType::Dorian,
+            ScaleType::Phrygian,
+            ScaleType::Lydian,
+            ScaleType::Mixolydian,
+            ScaleType::Minor,
+            ScaleType::Locrian,
+        ];
+        if let Some(first) = diatonic_modes.iter().position(|mode| *mode == self.scale_type) {
+            return notes.into_iter().enumerate()
+                .map(|(degree, root)| Scale::new(root, diatonic_modes[(first + degree) % 7].clone()))
+                .collect();
+        }
+        match &self.scale_type {
+            ScaleType::Custom(steps) => Scale::rotated_modes(&notes, steps),
+            ScaleType::Altered | ScaleType::LydianDominant => {
+                let offsets = self.semitone_offsets();
+                let steps: Vec<u8> = offsets.iter().zip(offsets.iter().skip(1).chain([&12])).map(|(low, high)| high - low).collect();
+                Scale::rotated_modes(&notes, &steps)
+            },
+            ScaleType::DiminishedWholeHalf | ScaleType::DiminishedHalfWhole => {
+                let other = if self.scale_type == ScaleType::DiminishedWholeHalf {
+                    ScaleType::DiminishedHalfWhole
+                }
+                else {
+                    ScaleType::DiminishedWholeHalf
+                };
+                notes.into_iter().enumerate()
+                    .map(|(degree, root)| Scale::new(root, if degree % 2 == 0 { self.scale_type.clone() } else { other.clone() }))
+                    .collect()
+            },
+            scale_type => notes.into_iter().map(|root| Scale::new(root, scale_type.clone())).collect(),
+        }
+    }
+
+    /// Custom scale on each of `notes` with `steps` rotated to start there.
+    fn rotated_modes(notes: &[Note], steps: &[u8]) -> Vec<Scale> {
+        (0..notes.len()).map(|degree| {
+            let mut rotated = steps.to_vec();
+            rotated.rotate_left(degree % steps.len().max(1));
+            Scale::new(notes[degree].clone(), ScaleType::Custom(rotated))
+        }).collect()
+    }
+
+    pub fn parallel(&self) -> Option<Scale> {
+        match self.scale_type {
+            ScaleType::Major => Some(Scale::new(self.root.clone(), ScaleType::Minor)),
+            ScaleType::Minor => Some(Scale::new(self.root.clone(), ScaleType::Major)),
+            _ => None,
+        }
+    }
+
+    /// Diatonic chords of the parallel key that are not diatonic to this one.
+    pub fn borrowed_chords(&self) -> Vec<Chord> {
+        let parallel = match self.parallel() {
+            Some(parallel) => parallel,
+            None => return Vec::new(),
+        };
+        let diatonic_chords = self.diatonic_chords().unwrap_or_default();
+        parallel.diatonic_chords().unwrap_or_default().into_iter().filter(|chord| !diatonic_chords.contains(chord)).collect()
+    }
+
+    pub fn related_keys(&self) -> Vec<Scale> {
+        let related = |interval, scale_type| Scale::new(self.root.up(interval), scale_type);
+        match &self.scale_type {
+            ScaleType::Major => vec![
+                related(Interval::perfect_fifth(), ScaleType::Major),
+                related(Interval::perfect_fourth(), ScaleType::Major),
+                related(Interval::major_sixth(), ScaleType::Minor),
+                related(Interval::major_third(), ScaleType::Minor),
+                related(Interval::major_second(), ScaleType::Minor),
+            ],
+            ScaleType::Minor => vec![
+                related(Interval::perfect_fifth(), ScaleType::Minor),
+                related(Interval::perfect_fourth(), ScaleType::Minor),
+                related(Interval::minor_third(), ScaleType::Major),
+                related(Interval::minor_seventh(), ScaleType::Major),
+                related(Interval::minor_sixth(), ScaleType::Major),
             ],
+            _ => vec![],
         }
     }
+
+    /// Modes other than major and minor are left out, as they share the notes
+    /// of their relative major.
+    pub fn from_notes(notes: &[Note]) -> Vec<Scale> {
+        let notes: HashSet<Note> = notes.iter().cloned().collect();
+        let mut possible_scales = Vec::new();
+        for white_note in WhiteNote::iter() {
+            for root in [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::Flat(white_note)].iter() {
+                for scale_type in ScaleType::iter().filter(|scale_type| !scale_type.is_mode()) {
+                    let scale = Scale::new(root.clone(), scale_type);
+                    let scale_notes: HashSet<Note> = scale.get_notes().into_iter().collect();
+                    if scale_notes == notes && !possible_scales.contains(&scale) {
+                        possible_scales.push(scale);
+                    }
+                }
+            }
+        }
+        possible_scales
+    }
+}
+
+pub fn scales_containing(chord: &Chord) -> Vec<Scale> {
+    let mut scales = Vec::new();
+    for white_note in WhiteNote::iter() {
+        for root in [Note::WhiteNote(white_note.clone()), Note::Sharp(white_note.clone()), Note::Flat(white_note)].iter() {
+            for scale_type in [ScaleType::Major, ScaleType::Minor] {
+                let scale = Scale::new(root.clone(), scale_type);
+                if scale.contains_chord(chord) && !scales.contains(&scale) {
+                    scales.push(scale);
+                }
+            }
+        }
+    }
+    scales
+}
+
+/// Name of the `degree`-th degree of a seven-note scale, or `None` for other
+/// scales and degrees outside 1 to 7.
+pub fn degree_function(degree: u8, scale_type: &ScaleType) -> Option<&'static str> {
+    let root = Note::WhiteNote(WhiteNote::C);
+    let notes = Scale::new(root.clone(), scale_type.clone()).get_notes();
+    if notes.len() != 7 {
+        return None;
+    }
+    match degree {
+        1 => Some("Tonic"),
+        2 => Some("Supertonic"),
+        3 => Some("Mediant"),
+        4 => Some("Subdominant"),
+        5 => Some("Dominant"),
+        6 => Some("Submediant"),
+        7 => Some(if root.semitone_distance(&notes[6]) == 11 { "Leading Tone" } else { "Subtonic" }),
+        _ => None,
+    }
 }
 
 #[cfg(test)]
@@ -92,6 +654,26 @@ mod test {
         let scale = Scale::from_str("Cb minor").unwrap();
         assert_eq!(scale.root, Note::Flat(WhiteNote::C));
         assert_eq!(scale.scale_type, ScaleType::Minor);
+
+        let scale = Scale::from_str("C Major").unwrap();
+        assert_eq!(scale.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(scale.scale_type, ScaleType::Major);
+
+        let scale = Scale::from_str("c MINOR").unwrap();
+        assert_eq!(scale.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(scale.scale_type, ScaleType::Minor);
+
+        let scale = Scale::from_str("bb Whole Tone").unwrap();
+        assert_eq!(scale.root, Note::Flat(WhiteNote::B));
+        assert_eq!(scale.scale_type, ScaleType::WholeTone);
+
+        let scale = Scale::from_str("C whole tone").unwrap();
+        assert_eq!(scale.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(scale.scale_type, ScaleType::WholeTone);
+
+        let scale = Scale::from_str("C chromatic").unwrap();
+        assert_eq!(scale.root, Note::WhiteNote(WhiteNote::C));
+        assert_eq!(scale.scale_type, ScaleType::Chromatic);
     }
 
     #[test]
@@ -139,5 +721,557 @@ mod test {
         assert_eq!(notes[4], Note::WhiteNote(WhiteNote::E));
         assert_eq!(notes[5], Note::Sharp(WhiteNote::F));
         assert_eq!(notes[6], Note::Sharp(WhiteNote::G));
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone);
+        let notes = scale.get_notes();
+        assert_eq!(notes.len(), 6);
+        assert_eq!(notes[0], Note::WhiteNote(WhiteNote::C));
+        assert_eq!(notes[1], Note::WhiteNote(WhiteNote::D));
+        assert_eq!(notes[2], Note::WhiteNote(WhiteNote::E));
+        assert_eq!(notes[3], Note::Sharp(WhiteNote::F));
+        assert_eq!(notes[4], Note::Sharp(WhiteNote::G));
+        assert_eq!(notes[5], Note::Sharp(WhiteNote::A));
+        assert_eq!(scale.to_string(), "C whole tone scale");
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Chromatic);
+        let notes = scale.get_notes();
+        assert_eq!(notes.len(), 12);
+        assert_eq!(notes.iter().collect::<HashSet<&Note>>().len(), 12);
+        assert_eq!(notes.iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" "), "C C# D D# E F F# G G# A A# B");
+    }
+
+    #[test]
+    fn test_scale_from_notes() {
+        let notes = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major).get_notes();
+        let scales = Scale::from_notes(&notes);
+        assert_eq!(scales.len(), 2);
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major)));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor)));
+
+        let notes = Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Major).get_notes();
+        let scales = Scale::from_notes(&notes);
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Major)));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::B), ScaleType::Minor)));
+
+        let notes = vec![Note::WhiteNote(WhiteNote::C), Note::WhiteNote(WhiteNote::E), Note::WhiteNote(WhiteNote::G)];
+        assert!(Scale::from_notes(&notes).is_empty());
+    }
+
+    #[test]
+    fn test_scale_diatonic_chords() {
+        let chords = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major).diatonic_chords();
+        assert_eq!(chords.unwrap(), vec![
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::E), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished),
+        ]);
+
+        let chords = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor).diatonic_chords();
+        assert_eq!(chords.unwrap(), vec![
+            Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::B), ChordQuality::Diminished),
+            Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::E), ChordQuality::Minor),
+            Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major),
+            Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major),
+        ]);
+
+        assert_eq!(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone).diatonic_chords(), None);
+        assert_eq!(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Chromatic).diatonic_chords(), None);
+    }
+
+    #[test]
+    fn test_scale_over_octaves() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        let notes = scale.notes_over_octaves(2);
+        assert_eq!(notes.len(), 15);
+        assert_eq!(notes[..7], scale.get_notes()[..]);
+        assert_eq!(notes[7..14], scale.get_notes()[..]);
+        assert_eq!(notes[14], Note::WhiteNote(WhiteNote::C));
+
+        let pitches = scale.pitches_over_octaves(2, 4).unwrap();
+        assert_eq!(pitches.len(), 15);
+        assert_eq!(pitches[0], (Note::WhiteNote(WhiteNote::C), 4));
+        assert_eq!(pitches[6], (Note::WhiteNote(WhiteNote::B), 4));
+        assert_eq!(pitches[7], (Note::WhiteNote(WhiteNote::C), 5));
+        assert_eq!(pitches[13], (Note::WhiteNote(WhiteNote::B), 5));
+        assert_eq!(pitches[14], (Note::WhiteNote(WhiteNote::C), 6));
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Major);
+        let pitches = scale.pitches_over_octaves(1, 3).unwrap();
+        assert_eq!(pitches, vec![
+            (Note::WhiteNote(WhiteNote::A), 3),
+            (Note::WhiteNote(WhiteNote::B), 3),
+            (Note::Sharp(WhiteNote::C), 4),
+            (Note::WhiteNote(WhiteNote::D), 4),
+            (Note::WhiteNote(WhiteNote::E), 4),
+            (Note::Sharp(WhiteNote::F), 4),
+            (Note::Sharp(WhiteNote::G), 4),
+            (Note::WhiteNote(WhiteNote::A), 4),
+        ]);
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(scale.pitches_over_octaves(1, 127), None);
+        assert_eq!(scale.pitches_over_octaves(1, 126).unwrap().last(), Some(&(Note::WhiteNote(WhiteNote::C), 127)));
+        let pitches = scale.pitches_over_octaves(200, -100).unwrap();
+        assert_eq!(pitches.len(), 1401);
+        assert_eq!(pitches.last(), Some(&(Note::WhiteNote(WhiteNote::C), 100)));
+        assert_eq!(scale.pitches_over_octaves(200, 0), None);
+        assert_eq!(scale.notes_over_octaves(200).len(), 1401);
+    }
+
+    #[test]
+    fn test_scale_transpose() {
+        let display = |scale: Scale| scale.get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+
+        let scale = Scale::from_str("C major").unwrap().transpose(Interval::new(IntervalQuality::Perfect, 4));
+        assert_eq!(scale, Scale::new(Note::WhiteNote(WhiteNote::F), ScaleType::Major));
+        assert_eq!(display(scale), "F G A Bb C D E");
+
+        let scale = Scale::from_str("C major").unwrap().transpose(Interval::new(IntervalQuality::Major, 3));
+        assert_eq!(display(scale), "E F# G# A B C# D#");
+
+        let scale = Scale::from_str("A minor").unwrap().transpose(Interval::new(IntervalQuality::Major, 2));
+        assert_eq!(display(scale), "B C# D E F# G A");
+
+        let scale = Scale::from_str("D minor").unwrap().transpose(Interval::new(IntervalQuality::Minor, 3));
+        assert_eq!(display(scale), "F G Ab Bb C Db Eb");
+    }
+
+    #[test]
+    fn test_scale_functional_chords() {
+        let scale = Scale::from_str("C major").unwrap();
+        assert_eq!(scale.tonic(), Some(Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major)));
+        assert_eq!(scale.subdominant(), Some(Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major)));
+        assert_eq!(scale.dominant(), Some(Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major)));
+
+        let scale = Scale::from_str("Bb major").unwrap();
+        assert_eq!(scale.tonic(), Some(Chord::new(Note::Flat(WhiteNote::B), ChordQuality::Major)));
+        assert_eq!(scale.subdominant(), Some(Chord::new(Note::Flat(WhiteNote::E), ChordQuality::Major)));
+        assert_eq!(scale.dominant(), Some(Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Major)));
+
+        let scale = Scale::from_str("A minor").unwrap();
+        assert_eq!(scale.tonic(), Some(Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::Minor)));
+        assert_eq!(scale.subdominant(), Some(Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor)));
+        assert_eq!(scale.dominant(), Some(Chord::new(Note::WhiteNote(WhiteNote::E), ChordQuality::Minor)));
+
+        let scale = Scale::from_str("C whole tone").unwrap();
+        assert_eq!(scale.tonic(), None);
+        assert_eq!(scale.dominant(), None);
+    }
+
+    #[test]
+    fn test_scale_related_keys() {
+        let keys = Scale::from_str("C major").unwrap().related_keys();
+        assert_eq!(keys, vec![
+            Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::F), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor),
+            Scale::new(Note::WhiteNote(WhiteNote::E), ScaleType::Minor),
+            Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Minor),
+        ]);
+
+        let keys = Scale::from_str("A minor").unwrap().related_keys();
+        assert_eq!(keys, vec![
+            Scale::new(Note::WhiteNote(WhiteNote::E), ScaleType::Minor),
+            Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Minor),
+            Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::F), ScaleType::Major),
+        ]);
+
+        assert!(Scale::from_str("C chromatic").unwrap().related_keys().is_empty());
+    }
+
+    #[test]
+    fn test_scale_spelling() {
+        let display = |scale: Scale| scale.get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+        assert_eq!(display(Scale::from_str("Gb major").unwrap()), "Gb Ab Bb Cb Db Eb F");
+        assert_eq!(display(Scale::from_str("Cb major").unwrap()), "Cb Db Eb Fb Gb Ab Bb");
+        assert_eq!(display(Scale::from_str("C# major").unwrap()), "C# D# E# F# G# A# B#");
+        assert_eq!(display(Scale::from_str("D# minor").unwrap()), "D# E# F# G# A# B C#");
+        assert_eq!(display(Scale::from_str("Eb minor").unwrap()), "Eb F Gb Ab Bb Cb Db");
+
+        let major_keys = ["C", "G", "D", "A", "E", "B", "F#", "C#", "F", "Bb", "Eb", "Ab", "Db", "Gb", "Cb"];
+        let minor_keys = ["A", "E", "B", "F#", "C#", "G#", "D#", "A#", "D", "G", "C", "F", "Bb", "Eb", "Ab"];
+        let keys = major_keys.iter().map(|root| (root, ScaleType::Major))
+            .chain(minor_keys.iter().map(|root| (root, ScaleType::Minor)));
+        for (root, scale_type) in keys {
+            let notes = Scale::new(Note::from_str(root).unwrap(), scale_type).get_notes();
+            let letters: HashSet<String> = notes.iter().map(|n| n.to_string()[..1].to_string()).collect();
+            assert_eq!(letters.len(), 7);
+        }
+    }
+
+    #[test]
+    fn test_from_intervals() {
+        let hungarian_minor = Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 1, 3, 1, 1, 3, 1]).unwrap();
+        let notes: Vec<String> = hungarian_minor.get_notes().iter().map(|n| n.to_string()).collect();
+        assert_eq!(notes.join(" "), "C D Eb F# G Ab B");
+
+        let hungarian_minor = Scale::from_intervals(Note::WhiteNote(WhiteNote::A), &[2, 1, 3, 1, 1, 3, 1]).unwrap();
+        let notes: Vec<String> = hungarian_minor.get_notes().iter().map(|n| n.to_string()).collect();
+        assert_eq!(notes.join(" "), "A B C D# E F G#");
+
+        let hungarian_minor = Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 1, 3, 1, 1, 3]).unwrap();
+        assert_eq!(hungarian_minor.get_notes().len(), 7);
+
+        assert_eq!(Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 2, 3, 2, 3, 1]), None);
+        assert_eq!(Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[200, 100]), None);
+        assert_eq!(Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 0, 3]), None);
+
+        let pentatonic = Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 2, 3, 2, 3]).unwrap();
+        assert_eq!(pentatonic.get_notes(), vec![
+            Note::WhiteNote(WhiteNote::C),
+            Note::WhiteNote(WhiteNote::D),
+            Note::WhiteNote(WhiteNote::E),
+            Note::WhiteNote(WhiteNote::G),
+            Note::WhiteNote(WhiteNote::A),
+        ]);
+        assert_eq!(pentatonic.to_string(), "C custom scale");
+    }
+
+    #[test]
+    fn test_get_notes_with_octave_tonic() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        let notes = scale.get_notes_with_octave_tonic();
+        assert_eq!(notes.len(), 8);
+        assert_eq!(notes[..7], scale.get_notes()[..]);
+        assert_eq!(notes.last(), Some(&Note::WhiteNote(WhiteNote::C)));
+
+        let scale = Scale::new(Note::Sharp(WhiteNote::F), ScaleType::Minor);
+        assert_eq!(scale.get_notes_with_octave_tonic().last(), Some(&Note::Sharp(WhiteNote::F)));
+    }
+
+    #[test]
+    fn test_scale_type_display() {
+        assert_eq!(ScaleType::Major.to_string(), "Major");
+        assert_eq!(ScaleType::Minor.to_string(), "Minor");
+        assert_eq!(ScaleType::WholeTone.to_string(), "Whole Tone");
+        assert_eq!(ScaleType::Chromatic.to_string(), "Chromatic");
+        assert_eq!(ScaleType::Custom(vec![2, 2, 3, 2, 3]).to_string(), "Custom");
+        assert_eq!(ScaleType::WholeTone.symbol(), "whole tone");
+        assert_eq!(Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::WholeTone).to_string(), "D whole tone scale");
+    }
+
+    #[test]
+    fn test_borrowed_chords() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(scale.parallel(), Some(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Minor)));
+        let borrowed = scale.borrowed_chords();
+        assert!(borrowed.contains(&Chord::new(Note::Flat(WhiteNote::B), ChordQuality::Major)));
+        assert!(borrowed.contains(&Chord::new(Note::WhiteNote(WhiteNote::F), ChordQuality::Minor)));
+        assert!(borrowed.contains(&Chord::new(Note::Flat(WhiteNote::A), ChordQuality::Major)));
+        assert!(!borrowed.contains(&Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::Major)));
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor);
+        let borrowed = scale.borrowed_chords();
+        assert!(borrowed.contains(&Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Major)));
+        assert!(!borrowed.contains(&Chord::new(Note::WhiteNote(WhiteNote::E), ChordQuality::Minor)));
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone);
+        assert_eq!(scale.parallel(), None);
+        assert!(scale.borrowed_chords().is_empty());
+    }
+
+    #[test]
+    fn test_degree_qualities() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(scale.degree_qualities().unwrap(), vec![
+            ChordQuality::Major,
+            ChordQuality::Minor,
+            ChordQuality::Minor,
+            ChordQuality::Major,
+            ChordQuality::Major,
+            ChordQuality::Minor,
+            ChordQuality::Diminished,
+        ]);
+
+        let scale = Scale::new(Note::Sharp(WhiteNote::F), ScaleType::Minor);
+        assert_eq!(scale.degree_qualities().unwrap(), vec![
+            ChordQuality::Minor,
+            ChordQuality::Diminished,
+            ChordQuality::Major,
+            ChordQuality::Minor,
+            ChordQuality::Minor,
+            ChordQuality::Major,
+            ChordQuality::Major,
+        ]);
+    }
+
+    #[test]
+    fn test_contains_chord() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert!(scale.contains(&Note::WhiteNote(WhiteNote::F)));
+        assert!(!scale.contains(&Note::Sharp(WhiteNote::F)));
+        assert!(scale.contains_chord(&Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Minor)));
+        assert!(scale.contains_chord(&Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh)));
+        assert!(!scale.contains_chord(&Chord::new(Note::WhiteNote(WhiteNote::D), ChordQuality::Major)));
+        assert!(!scale.contains_chord(&Chord::new(Note::Flat(WhiteNote::B), ChordQuality::Major)));
+
+        let scale = Scale::new(Note::Flat(WhiteNote::E), ScaleType::Major);
+        assert!(scale.contains_chord(&Chord::new(Note::Sharp(WhiteNote::G), ChordQuality::Major)));
+    }
+
+    #[test]
+    fn test_modes_notes() {
+        let names = |s: &str| Scale::from_str(s).unwrap().get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+        assert_eq!(names("D dorian"), "D E F G A B C");
+        assert_eq!(names("E phrygian"), "E F G A B C D");
+        assert_eq!(names("F lydian"), "F G A B C D E");
+        assert_eq!(names("G mixolydian"), "G A B C D E F");
+        assert_eq!(names("B locrian"), "B C D E F G A");
+        assert_eq!(names("C ionian"), names("C major"));
+        assert_eq!(names("A aeolian"), names("A minor"));
+        assert_eq!(names("C lydian"), "C D E F# G A B");
+    }
+
+    #[test]
+    fn test_brightness() {
+        let mut modes = vec![
+            ScaleType::Locrian,
+            ScaleType::Major,
+            ScaleType::Phrygian,
+            ScaleType::Lydian,
+            ScaleType::Minor,
+            ScaleType::Mixolydian,
+            ScaleType::Dorian,
+        ];
+        modes.sort_by_key(|mode| std::cmp::Reverse(mode.brightness()));
+        assert_eq!(modes, vec![
+            ScaleType::Lydian,
+            ScaleType::Major,
+            ScaleType::Mixolydian,
+            ScaleType::Dorian,
+            ScaleType::Minor,
+            ScaleType::Phrygian,
+            ScaleType::Locrian,
+        ]);
+        assert_eq!(ScaleType::Lydian.brightness(), 1);
+        assert_eq!(ScaleType::Major.brightness(), 0);
+        assert_eq!(ScaleType::Locrian.brightness(), -5);
+        assert_eq!(ScaleType::WholeTone.brightness(), 0);
+    }
+
+    #[test]
+    fn test_scales_containing() {
+        let scales = scales_containing(&Chord::new(Note::WhiteNote(WhiteNote::C), ChordQuality::Major));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major)));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Major)));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::F), ScaleType::Major)));
+        assert!(scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor)));
+        assert!(!scales.contains(&Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Major)));
+        assert_eq!(scales.len(), 6);
+
+        let scales = scales_containing(&Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh));
+        assert_eq!(scales, vec![
+            Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor),
+        ]);
+    }
+
+    #[test]
+    fn test_degree_function() {
+        let functions: Vec<&str> = (1..=7).filter_map(|degree| degree_function(degree, &ScaleType::Major)).collect();
+        assert_eq!(functions, vec!["Tonic", "Supertonic", "Mediant", "Subdominant", "Dominant", "Submediant", "Leading Tone"]);
+        let functions: Vec<&str> = (1..=7).filter_map(|degree| degree_function(degree, &ScaleType::Minor)).collect();
+        assert_eq!(functions, vec!["Tonic", "Supertonic", "Mediant", "Subdominant", "Dominant", "Submediant", "Subtonic"]);
+        assert_eq!(degree_function(7, &ScaleType::Lydian), Some("Leading Tone"));
+        assert_eq!(degree_function(7, &ScaleType::Mixolydian), Some("Subtonic"));
+        assert_eq!(degree_function(0, &ScaleType::Major), None);
+        assert_eq!(degree_function(8, &ScaleType::Major), None);
+        assert_eq!(degree_function(7, &ScaleType::WholeTone), None);
+        assert_eq!(degree_function(1, &ScaleType::WholeTone), None);
+    }
+
+    #[test]
+    fn test_modes() {
+        let modes = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major).modes();
+        assert_eq!(modes, vec![
+            Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major),
+            Scale::new(Note::WhiteNote(WhiteNote::D), ScaleType::Dorian),
+            Scale::new(Note::WhiteNote(WhiteNote::E), ScaleType::Phrygian),
+            Scale::new(Note::WhiteNote(WhiteNote::F), ScaleType::Lydian),
+            Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Mixolydian),
+            Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor),
+            Scale::new(Note::WhiteNote(WhiteNote::B), ScaleType::Locrian),
+        ]);
+        let c_notes: HashSet<Note> = modes[0].get_notes().into_iter().collect();
+        for mode in modes.iter() {
+            assert_eq!(mode.get_notes().into_iter().collect::<HashSet<Note>>(), c_notes);
+        }
+
+        let modes = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor).modes();
+        assert_eq!(modes[1], Scale::new(Note::WhiteNote(WhiteNote::B), ScaleType::Locrian));
+        assert_eq!(modes[2], Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major));
+
+        let modes = Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[2, 2, 3, 2, 3]).unwrap().modes();
+        assert_eq!(modes[1], Scale::from_intervals(Note::WhiteNote(WhiteNote::D), &[2, 3, 2, 3, 2]).unwrap());
+
+        let modes = Scale::from_str("C lydian dominant").unwrap().modes();
+        let names: Vec<String> = modes[1].get_notes().iter().map(|note| note.to_string()).collect();
+        assert_eq!(names.join(" "), "D E F# G A Bb C");
+
+        let modes = Scale::from_str("C half-whole diminished").unwrap().modes();
+        assert_eq!(modes[1], Scale::new(Note::Flat(WhiteNote::D), ScaleType::DiminishedWholeHalf));
+        assert_eq!(modes[2], Scale::new(Note::Flat(WhiteNote::E), ScaleType::DiminishedHalfWhole));
+
+        for scale_type in [ScaleType::Altered, ScaleType::LydianDominant, ScaleType::DiminishedWholeHalf, ScaleType::DiminishedHalfWhole] {
+            let scale = Scale::new(Note::WhiteNote(WhiteNote::C), scale_type);
+            let notes: HashSet<Note> = scale.get_notes().into_iter().collect();
+            for mode in scale.modes() {
+                assert_eq!(mode.get_notes().into_iter().collect::<HashSet<Note>>(), notes);
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_pattern() {
+        let pattern = |scale: Scale| scale.step_pattern().iter().map(|step| step.to_string()).collect::<Vec<String>>().join(" ");
+        assert_eq!(pattern(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major)), "W W H W W W H");
+        assert_eq!(pattern(Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor)), "W H W W H W W");
+        assert_eq!(pattern(Scale::from_intervals(Note::WhiteNote(WhiteNote::A), &[2, 1, 2, 2, 1, 3, 1]).unwrap()), "W H W W H A H");
+        assert_eq!(pattern(Scale::from_intervals(Note::WhiteNote(WhiteNote::C), &[4, 3, 5]).unwrap()), "4 A 5");
+        assert_eq!(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone).step_pattern(), vec![Step::Whole; 6]);
+    }
+
+    #[test]
+    fn test_scale_accessors() {
+        let scale = Scale::new(Note::Sharp(WhiteNote::F), ScaleType::Dorian);
+        assert_eq!(scale.root(), &Note::Sharp(WhiteNote::F));
+        assert_eq!(scale.scale_type(), &ScaleType::Dorian);
+    }
+
+    #[test]
+    fn test_semitone_offsets() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        assert_eq!(Scale::new(c.clone(), ScaleType::Major).semitone_offsets(), vec![0, 2, 4, 5, 7, 9, 11]);
+        assert_eq!(Scale::new(c.clone(), ScaleType::Minor).semitone_offsets(), vec![0, 2, 3, 5, 7, 8, 10]);
+        assert_eq!(Scale::from_intervals(c.clone(), &[2, 1, 2, 2, 1, 3, 1]).unwrap().semitone_offsets(), vec![0, 2, 3, 5, 7, 8, 11]);
+        for scale_type in ScaleType::iter() {
+            let scale = Scale::new(Note::Flat(WhiteNote::E), scale_type);
+            let offsets: Vec<u8> = scale.get_notes().iter().map(|note| scale.root().semitone_distance(note)).collect();
+            assert_eq!(scale.semitone_offsets(), offsets);
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let c = Note::WhiteNote(WhiteNote::C);
+        let major = Scale::new(c.clone(), ScaleType::Major);
+        let mixolydian = Scale::new(c.clone(), ScaleType::Mixolydian);
+        assert_eq!(major.diff(&mixolydian), (vec![Note::WhiteNote(WhiteNote::B)], vec![Note::Flat(WhiteNote::B)]));
+        assert_eq!(major.diff(&major), (vec![], vec![]));
+        let a_minor = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor);
+        assert_eq!(major.diff(&a_minor), (vec![], vec![]));
+    }
+
+    #[test]
+    fn test_in_intervals() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        let thirds: Vec<String> = scale.in_thirds().iter().map(|note| note.to_string()).collect();
+        assert_eq!(thirds, vec!["C", "E", "D", "F", "E", "G", "F", "A", "G", "B", "A", "C", "B", "D"]);
+        let fourths: Vec<String> = scale.in_intervals(4).iter().take(4).map(|note| note.to_string()).collect();
+        assert_eq!(fourths, vec!["C", "F", "D", "G"]);
+        assert!(scale.in_intervals(0).is_empty());
+    }
+
+    #[test]
+    fn test_keywords() {
+        let keywords = ScaleType::keywords();
+        for scale_type in ScaleType::iter() {
+            let (_, keyword) = keywords.iter().find(|(other, _)| *other == scale_type).unwrap();
+            assert_eq!(Scale::from_str(&format!("C {}", keyword)), Some(Scale::new(Note::WhiteNote(WhiteNote::C), scale_type)));
+        }
+        assert_eq!(Scale::from_str("A Aeolian"), Some(Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor)));
+        assert_eq!(Scale::from_str("G super locrian"), Some(Scale::new(Note::WhiteNote(WhiteNote::G), ScaleType::Altered)));
+        assert_eq!(keywords.len(), ScaleType::iter().count() + 3);
+    }
+
+    #[test]
+    fn test_intervals() {
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(scale.intervals().unwrap(), vec![
+            Interval::perfect_unison(),
+            Interval::major_second(),
+            Interval::major_third(),
+            Interval::perfect_fourth(),
+            Interval::perfect_fifth(),
+            Interval::major_sixth(),
+            Interval::major_seventh(),
+        ]);
+        let scale = Scale::new(Note::Sharp(WhiteNote::F), ScaleType::Locrian);
+        assert_eq!(scale.intervals().unwrap()[4], Interval::diminished_fifth());
+        assert_eq!(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Chromatic).intervals().unwrap().len(), 12);
+        assert_eq!(Scale::new(Note::DoubleSharp(WhiteNote::B), ScaleType::Major).intervals(), None);
+    }
+
+    #[test]
+    fn test_full_harmony() {
+        let harmony = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major).full_harmony().unwrap();
+        assert_eq!(harmony.len(), 14);
+        let labels: Vec<String> = harmony.iter().map(|(numeral, chord)| format!("{}: {}", numeral, chord)).collect();
+        assert_eq!(labels[0], "I: C");
+        assert_eq!(labels[1], "Imaj7: Cmaj7");
+        assert_eq!(labels[3], "ii7: Dm7");
+        assert_eq!(labels[9], "V7: G7");
+        assert_eq!(labels[13], "viiø7: Bm7b5");
+
+        let harmony = Scale::new(Note::WhiteNote(WhiteNote::A), ScaleType::Minor).full_harmony().unwrap();
+        assert_eq!(harmony[1].1, Chord::new(Note::WhiteNote(WhiteNote::A), ChordQuality::MinorSeventh));
+        assert_eq!(harmony[5].0, "IIImaj7");
+
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Major);
+        assert_eq!(scale.diatonic_sevenths().unwrap()[4], Chord::new(Note::WhiteNote(WhiteNote::G), ChordQuality::DominantSeventh));
+        let scale = Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::WholeTone);
+        assert_eq!(scale.diatonic_sevenths(), None);
+        assert_eq!(scale.full_harmony(), None);
+    }
+
+    #[test]
+    fn test_jazz_scales() {
+        let names = |s: &str| Scale::from_str(s).unwrap().get_notes().iter().map(|n| n.to_string()).collect::<Vec<String>>().join(" ");
+        assert_eq!(names("C altered"), "C Db Eb Fb Gb Ab Bb");
+        assert_eq!(names("C super locrian"), names("C altered"));
+        assert_eq!(names("C lydian dominant"), "C D E F# G A Bb");
+        assert_eq!(names("C half-whole diminished"), "C Db Eb E F# G A Bb");
+        assert_eq!(names("C whole-half diminished"), "C D Eb F Gb Ab A B");
+        let step_pattern = |s: &str| Scale::from_str(s).unwrap().step_pattern().iter().map(|step| step.to_string()).collect::<String>();
+        assert_eq!(step_pattern("C half-whole diminished"), "HWHWHWHW");
+        assert_eq!(step_pattern("C whole-half diminished"), "WHWHWHWH");
+        assert_eq!(Scale::from_str("C lydian"), Some(Scale::new(Note::WhiteNote(WhiteNote::C), ScaleType::Lydian)));
+    }
+
+    #[test]
+    fn test_string_round_trip() {
+        for scale_type in ScaleType::iter() {
+            assert_eq!(ScaleType::from_symbol(scale_type.symbol()), Some(scale_type.clone()));
+            for root in [Note::WhiteNote(WhiteNote::C), Note::Sharp(WhiteNote::F), Note::Flat(WhiteNote::B)] {
+                let scale = Scale::new(root, scale_type.clone());
+                let parsed = Scale::from_str(&scale.to_string()).unwrap();
+                assert_eq!(parsed, scale);
+                assert_eq!(parsed.to_string(), scale.to_string());
+            }
+        }
+        assert_eq!(ScaleType::from_symbol("Aeolian"), Some(ScaleType::Minor));
+        assert_eq!(ScaleType::from_symbol("blues"), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        for scale in [Scale::from_str("Eb dorian").unwrap(), Scale::from_intervals(Note::WhiteNote(WhiteNote::A), &[2, 1, 3, 1, 1, 3, 1]).unwrap()] {
+            let json = serde_json::to_string(&scale).unwrap();
+            let parsed: Scale = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, scale);
+            assert_eq!(parsed.root().to_string(), scale.root().to_string());
+        }
+        let json = serde_json::to_string(&ScaleType::WholeTone).unwrap();
+        assert_eq!(serde_json::from_str::<ScaleType>(&json).unwrap(), ScaleType::WholeTone);
     }
 }
@@ -0,0 +1,197 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+fn cameron(args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_cameron"))
+        .args(args)
+        .output()
+        .unwrap();
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_chord_midi() {
+    assert_eq!(cameron(&["chord", "C", "--midi"]), "60 64 67\n");
+    assert_eq!(cameron(&["chord", "C", "--midi", "--octave", "3"]), "48 52 55\n");
+    assert_eq!(cameron(&["chord", "A", "--midi"]), "69 73 76\n");
+}
+
+#[test]
+fn test_scale_midi() {
+    assert_eq!(cameron(&["scale", "C major", "--midi"]), "60 62 64 65 67 69 71\n");
+    assert_eq!(cameron(&["scale", "C major", "--midi", "--octave", "5"]), "72 74 76 77 79 81 83\n");
+}
+
+#[test]
+fn test_progression() {
+    assert_eq!(cameron(&["progression", "I V vi IV"]), "C: C E G\nG: G B D\nAm: A C E\nF: F A C\n");
+    assert_eq!(cameron(&["progression", "I V vi IV", "--key", "G"]), "G: G B D\nD: D F# A\nEm: E G B\nC: C E G\n");
+    assert_eq!(cameron(&["progression", "i iv V", "--key", "A minor"]), "Am: A C E\nDm: D F A\nE: E G# B\n");
+    assert_eq!(cameron(&["progression", "VII", "--key", "C whole tone"]), "Invalid progression provided.\n");
+}
+
+#[test]
+fn test_harmonize() {
+    assert_eq!(cameron(&["harmonize", "C major"]), "I: C\nii: Dm\niii: Em\nIV: F\nV: G\nvi: Am\nvii°: Bdim\n");
+    assert_eq!(cameron(&["harmonize", "A minor"]), "i: Am\nii°: Bdim\nIII: C\niv: Dm\nv: Em\nVI: F\nVII: G\n");
+    assert_eq!(cameron(&["harmonize", "C whole tone"]), "This scale cannot be harmonized in thirds.\n");
+    assert_eq!(cameron(&["harmonize", "C chromatic"]), "This scale cannot be harmonized in thirds.\n");
+    assert_eq!(cameron(&["harmonize", "C half-whole diminished"]).lines().last(), Some("8: Bbdim"));
+    assert_eq!(cameron(&["harmonize", "C half-whole diminished"]).lines().count(), 8);
+    assert_eq!(cameron(&["harmonize", "C whole-half diminished"]).lines().count(), 8);
+    assert_eq!(cameron(&["harmonize", "C whole-half diminished"]).lines().nth(1), Some("ii°: Ddim"));
+    assert_eq!(cameron(&["harmonize", "C altered"]), "This scale cannot be harmonized in thirds.\n");
+    assert_eq!(cameron(&["harmonize", "C lydian dominant"]), "This scale cannot be harmonized in thirds.\n");
+}
+
+#[test]
+fn test_scale_descending() {
+    assert_eq!(cameron(&["scale", "C major"]), "C D E F G A B\n");
+    assert_eq!(cameron(&["scale", "C major", "--descending"]), "B A G F E D C\n");
+    assert_eq!(cameron(&["scale", "C major", "--descending", "--midi"]), "71 69 67 65 64 62 60\n");
+}
+
+#[test]
+fn test_frequencies() {
+    let output = cameron(&["frequencies", "A major"]);
+    assert_eq!(output.lines().next(), Some("A4  440.00"));
+    assert_eq!(output.lines().nth(2), Some("C#5  554.37"));
+
+    let output = cameron(&["frequencies", "C major", "--octave", "4"]);
+    assert_eq!(output.lines().next(), Some("C4  261.63"));
+    assert_eq!(output.lines().nth(5), Some("A4  440.00"));
+
+    assert_eq!(cameron(&["frequencies", "C major", "--octave", "127"]), "Invalid octave provided.\n");
+    assert_eq!(cameron(&["frequencies", "C major", "--octave", "-128"]), "Invalid octave provided.\n");
+    assert_eq!(cameron(&["frequencies", "C major", "--reference", "0"]), "Invalid reference frequency provided.\n");
+    assert_eq!(cameron(&["frequencies", "C major", "--reference", "-440"]), "Invalid reference frequency provided.\n");
+
+    let output = cameron(&["frequencies", "A minor", "--octave", "3", "--reference", "432"]);
+    assert_eq!(output.lines().next(), Some("A3  216.00"));
+}
+
+#[test]
+fn test_chord_errors() {
+    assert_eq!(cameron(&["chord", "Cmaj7"]), "C E G B\n");
+    assert_eq!(cameron(&["chord", ""]), "Invalid chord provided: empty chord.\n");
+    assert_eq!(cameron(&["chord", "H"]), "Invalid chord provided: invalid root note.\n");
+    assert_eq!(cameron(&["chord", "Cxyz"]), "Invalid chord provided: unknown chord quality.\n");
+}
+
+#[test]
+fn test_stack() {
+    assert_eq!(cameron(&["stack", "C", "M3", "P5"]), "C E G\n");
+    assert_eq!(cameron(&["stack", "C", "M3", "P5", "m7"]), "C E G Bb\n");
+    assert_eq!(cameron(&["stack", "D", "m3", "d5"]), "D F Ab\n");
+    assert_eq!(cameron(&["stack", "C", "P3"]), "Invalid interval provided.\n");
+    assert_eq!(cameron(&["stack", "C", "M254"]), "Invalid interval provided.\n");
+}
+
+#[test]
+fn test_scale_with_octave() {
+    assert_eq!(cameron(&["scale", "C major", "--with-octave"]), "C D E F G A B C\n");
+    assert_eq!(cameron(&["scale", "C major", "--with-octave", "--midi"]), "60 62 64 65 67 69 71 72\n");
+    assert_eq!(cameron(&["scale", "A minor", "--with-octave", "--descending"]), "A G F E D C B A\n");
+}
+
+#[test]
+fn test_scale_highlight() {
+    let output = cameron(&["scale", "C major", "--highlight", "Cmaj7"]);
+    let highlighted: Vec<&str> = output.trim().split(' ')
+        .filter(|note| note.starts_with("\x1b[1;32m"))
+        .map(|note| note.trim_start_matches("\x1b[1;32m").trim_end_matches("\x1b[0m"))
+        .collect();
+    assert_eq!(highlighted, vec!["C", "E", "G", "B"]);
+    assert_eq!(output.replace("\x1b[1;32m", "").replace("\x1b[0m", ""), "C D E F G A B\n");
+    assert_eq!(cameron(&["scale", "C major", "--highlight", "H"]), "Invalid chord provided: invalid root note.\n");
+}
+
+#[test]
+fn test_completions() {
+    let bash = cameron(&["completions", "bash"]);
+    assert!(!bash.is_empty());
+    assert!(bash.contains("scale"));
+    assert!(bash.contains("--with-octave"));
+    assert!(bash.contains("complete -F _cameron cameron"));
+    assert!(!bash.contains("completions"));
+
+    let fish = cameron(&["completions", "fish"]);
+    assert!(fish.contains("-a scale"));
+    assert!(fish.contains("-l midi"));
+
+    let zsh = cameron(&["completions", "zsh"]);
+    assert!(zsh.starts_with("#compdef cameron"));
+    assert!(zsh.contains("'scale:"));
+    assert!(zsh.contains("'--with-octave["));
+    assert!(zsh.contains("compdef _cameron cameron"));
+    assert!(!zsh.contains("completions"));
+}
+
+#[test]
+fn test_spelling() {
+    assert_eq!(cameron(&["scale", "A major", "--spelling", "flats"]), "A B Db D E Gb Ab\n");
+    assert_eq!(cameron(&["scale", "A major", "--spelling", "sharps"]), "A B C# D E F# G#\n");
+    assert_eq!(cameron(&["scale", "C major", "--spelling", "flats"]), "C D E F G A B\n");
+    assert_eq!(cameron(&["scale", "Eb major", "--spelling", "sharps"]), "D# F G G# A# C D\n");
+    assert_eq!(cameron(&["chord", "F#", "--spelling", "flats"]), "Gb Bb Db\n");
+}
+
+#[test]
+fn test_abc_format() {
+    assert_eq!(cameron(&["scale", "C major", "--format", "abc"]), "C D E F G A B\n");
+    assert_eq!(cameron(&["scale", "C major", "--format", "abc", "--with-octave"]), "C D E F G A B c\n");
+    assert_eq!(cameron(&["scale", "E major", "--format", "abc"]), "E ^F ^G A B ^c ^d\n");
+    assert_eq!(cameron(&["chord", "Bb7", "--format", "abc", "--octave", "3"]), "_B, D F _A\n");
+}
+
+#[test]
+fn test_lilypond_format() {
+    assert_eq!(cameron(&["scale", "Bb major", "--format", "lilypond"]), "bes' c'' d'' ees'' f'' g'' a''\n");
+    assert_eq!(cameron(&["chord", "C#m", "--format", "lilypond", "--octave", "3"]), "cis e gis\n");
+    assert_eq!(cameron(&["scale", "C major", "--format", "lilypond", "--octave", "9"]), "Invalid octave provided.\n");
+    assert_eq!(cameron(&["chord", "C", "--format", "abc", "--octave", "127"]), "Invalid octave provided.\n");
+    assert_eq!(cameron(&["chord", "C", "--format", "musicxml", "--octave", "-2"]), "Invalid octave provided.\n");
+}
+
+#[test]
+fn test_repl() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_cameron"))
+        .arg("repl")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"chord Cmaj7\nscale D dorian --midi\n\nstack C M3 P5\nidentify A C E\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "C E G B\n62 64 65 67 69 71 72\nC E G\nAm\n");
+}
+
+#[test]
+fn test_musicxml_format() {
+    assert_eq!(
+        cameron(&["scale", "F major", "--format", "musicxml"]),
+        "<pitch><step>F</step><octave>4</octave></pitch>\n\
+        <pitch><step>G</step><octave>4</octave></pitch>\n\
+        <pitch><step>A</step><octave>4</octave></pitch>\n\
+        <pitch><step>B</step><alter>-1</alter><octave>4</octave></pitch>\n\
+        <pitch><step>C</step><octave>5</octave></pitch>\n\
+        <pitch><step>D</step><octave>5</octave></pitch>\n\
+        <pitch><step>E</step><octave>5</octave></pitch>\n"
+    );
+}
+
+#[test]
+fn test_identify() {
+    assert_eq!(cameron(&["identify", "60", "64", "67"]), "C\n");
+    assert_eq!(cameron(&["identify", "G", "71", "D", "65"]), "G7\n");
+    assert_eq!(cameron(&["identify", "C", "D"]), "No chord found.\n");
+    assert_eq!(cameron(&["identify", "C", "200"]), "Invalid note provided.\n");
+}
+
+#[test]
+fn test_chord_transpose() {
+    assert_eq!(cameron(&["chord", "Cmaj7", "--transpose", "P5"]), "G B D F#\n");
+    assert_eq!(cameron(&["chord", "Cmaj7", "--transpose", "P5", "--symbol"]), "Gmaj7\n");
+    assert_eq!(cameron(&["chord", "Am", "--transpose", "m3", "--symbol"]), "Cm\n");
+    assert_eq!(cameron(&["chord", "C", "--transpose", "P3"]), "Invalid interval provided.\n");
+}